@@ -1 +1,2 @@
-pub mod window;
\ No newline at end of file
+pub mod stats_overlay;
+pub mod wgpu_canvas;
\ No newline at end of file
@@ -0,0 +1,48 @@
+use leptos::prelude::*;
+
+use crate::utils::types::render_stats::RenderStats;
+
+/// A small readout of [`RenderStats`], meant to be placed over a
+/// [`WgpuCanvas`](crate::WgpuCanvas) inside a positioned container.
+///
+/// ```ignore
+/// let (stats, set_stats) = signal(RenderStats::default());
+/// view! {
+///     <WgpuCanvas<MyScene> stats=set_stats />
+///     <StatsOverlay stats=stats />
+/// }
+/// ```
+#[allow(non_snake_case)]
+#[component]
+pub fn StatsOverlay(
+    /// The stats published by the canvas.
+    #[prop(into)]
+    stats: Signal<RenderStats>,
+    /// Class for the overlay element, `stats-overlay` by default.
+    #[prop(into, default = "stats-overlay".to_string())]
+    class: String,
+) -> impl IntoView {
+    view! {
+        <div class=class>
+            <div>{move || format!("{:.0} fps", stats.get().fps)}</div>
+            <div>{move || format!("{:.2} ms", stats.get().frame_time_ms)}</div>
+            <div>
+                {move || {
+                    let size = stats.get().surface_size;
+                    format!("{} × {}", size.width, size.height)
+                }}
+            </div>
+            <div>
+                {move || {
+                    stats.with(|stats| {
+                        if stats.adapter_name.is_empty() {
+                            stats.backend.clone()
+                        } else {
+                            format!("{} ({})", stats.adapter_name, stats.backend)
+                        }
+                    })
+                }}
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,663 @@
+use std::cell::{Cell, RefCell};
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use leptos::{html::Canvas, prelude::*};
+use leptos::wasm_bindgen::JsCast;
+use send_wrapper::SendWrapper;
+use wasm_bindgen_futures::spawn_local;
+use wasm_bindgen_futures::wasm_bindgen::prelude::Closure;
+
+use crate::utils::helpers::callbacks::*;
+use crate::utils::helpers::canvas::{fit_canvas, scale_factor};
+use crate::utils::helpers::gamepad::poll_gamepads;
+use crate::utils::renderer::Renderer;
+use crate::utils::types::action_map::ActiveActions;
+use crate::utils::types::frame_time::FrameClock;
+use crate::utils::types::render_mode::{RedrawHandle, RenderMode};
+use crate::utils::types::render_stats::RenderStats;
+use crate::utils::types::render_status::{Recovery, RenderStatus};
+use crate::utils::types::size::PhysicalSize;
+
+type FrameCallback = Rc<RefCell<Option<Closure<dyn FnMut(f64)>>>>;
+
+/// Everything a mounted canvas keeps alive. Dropping it stops the render loop,
+/// removes the event listeners and releases the renderer along with its GPU resources.
+struct RenderLoop<R: Renderer> {
+    window: Rc<leptos::web_sys::Window>,
+    frame_id: Rc<Cell<Option<i32>>>,
+    frame_callback: FrameCallback,
+    listeners: Vec<EventListener>,
+    observers: Vec<Observer>,
+    renderer: Rc<RefCell<R>>,
+}
+
+impl<R: Renderer> Drop for RenderLoop<R> {
+    fn drop(&mut self) {
+        if let Some(id) = self.frame_id.take() {
+            let _ = self.window.cancel_animation_frame(id);
+        }
+
+        // the frame closure holds a reference to its own cell, so it has to be taken out to break the cycle
+        self.frame_callback.borrow_mut().take();
+        self.listeners.clear();
+        self.observers.clear();
+
+        if Rc::strong_count(&self.renderer) > 1 {
+            leptos::logging::warn!("Renderer is still referenced after its canvas was unmounted");
+        }
+    }
+}
+
+/// Forwards [`RenderStatus`] changes to the `status` prop, skipping repeats so that a
+/// healthy loop does not notify its subscribers on every frame.
+#[derive(Clone)]
+struct StatusReporter {
+    signal: Option<WriteSignal<RenderStatus>>,
+    last: Rc<RefCell<Option<RenderStatus>>>,
+}
+
+impl StatusReporter {
+    fn report(&self, status: RenderStatus) {
+        let Some(signal) = self.signal else {
+            return;
+        };
+
+        let mut last = self.last.borrow_mut();
+        if last.as_ref() != Some(&status) {
+            *last = Some(status.clone());
+            signal.try_set(status);
+        }
+    }
+}
+
+/// Measures frame times and publishes [`RenderStats`] to the `stats` prop, throttled so the
+/// page re-renders a few times per second rather than on every frame.
+struct StatsPublisher {
+    signal: Option<WriteSignal<RenderStats>>,
+    performance: Option<leptos::web_sys::Performance>,
+    last_frame: Option<f64>,
+    last_publish: f64,
+    // exponential moving average of the frame time, in milliseconds
+    average_frame_time: f64,
+}
+
+impl StatsPublisher {
+    const PUBLISH_INTERVAL_MS: f64 = 250.0;
+    // weight of the newest frame; about a second of history at 60 fps
+    const SMOOTHING: f64 = 0.05;
+
+    fn new(signal: Option<WriteSignal<RenderStats>>, window: &leptos::web_sys::Window) -> Self {
+        Self {
+            signal,
+            performance: window.performance(),
+            last_frame: None,
+            last_publish: f64::NEG_INFINITY,
+            average_frame_time: 0.0,
+        }
+    }
+
+    /// Starts timing over after the loop was suspended.
+    fn reset(&mut self) {
+        self.last_frame = None;
+    }
+
+    fn frame<R: Renderer>(&mut self, renderer: &R, canvas: &leptos::web_sys::HtmlCanvasElement) {
+        let Some(signal) = self.signal else {
+            return;
+        };
+        let now = self.performance.as_ref().map_or(0.0, |performance| performance.now());
+
+        let frame_time = self.last_frame.map_or(0.0, |last| now - last);
+        self.last_frame = Some(now);
+        self.average_frame_time = if self.average_frame_time == 0.0 {
+            frame_time
+        } else {
+            self.average_frame_time + (frame_time - self.average_frame_time) * Self::SMOOTHING
+        };
+
+        if now - self.last_publish < Self::PUBLISH_INTERVAL_MS {
+            return;
+        }
+        self.last_publish = now;
+
+        let (adapter_name, backend) = renderer
+            .adapter_info()
+            .map(|info| (info.name.clone(), format!("{:?}", info.backend)))
+            .unwrap_or_default();
+
+        signal.try_set(RenderStats {
+            frame_time_ms: frame_time,
+            fps: if self.average_frame_time > 0.0 { 1000.0 / self.average_frame_time } else { 0.0 },
+            adapter_name,
+            backend,
+            surface_size: PhysicalSize {
+                width: canvas.width(),
+                height: canvas.height(),
+            },
+        });
+    }
+}
+
+/// A canvas that sizes itself, builds a `R` once mounted and drives it from a
+/// `request_animation_frame` loop until it is unmounted.
+///
+/// If [`Renderer::init`] fails the error is thrown to the nearest `<ErrorBoundary/>`;
+/// remounting the component retries the initialization. Surface errors during rendering are
+/// recovered from where possible, and an out-of-memory error stops the loop and is thrown the same way.
+///
+/// ```ignore
+/// view! { <WgpuCanvas<MyScene> style="width: 50vw; height: 50vh; display: block" /> }
+/// ```
+#[allow(non_snake_case)]
+#[component]
+pub fn WgpuCanvas<R: Renderer>(
+    /// Inline style for the canvas element, which also decides its size on the page.
+    #[prop(into, default = "width: 100vw; height: 100vh; display: block".to_string())]
+    style: String,
+    /// Optional class for the canvas element.
+    #[prop(into, optional)]
+    class: String,
+    /// Passed to [`Renderer::init`]; the renderer's default config when left out.
+    #[prop(optional)]
+    config: Option<R::Config>,
+    /// Receives the [`RenderStatus`] whenever it changes.
+    #[prop(optional, into)]
+    status: Option<WriteSignal<RenderStatus>>,
+    /// Receives frame timing, adapter and surface size a few times per second.
+    #[prop(optional, into)]
+    stats: Option<WriteSignal<RenderStats>>,
+    /// Locks the pointer to the canvas when it is clicked while this is `true`, so the renderer
+    /// gets relative mouse motion for first person navigation. Escape releases it again, and so
+    /// does switching this to `false`.
+    #[prop(optional, into)]
+    pointer_lock: Option<Signal<bool>>,
+    /// Listens for keys on the whole window instead of only while the canvas has focus.
+    /// Keys typed into text fields are ignored either way.
+    #[prop(optional)]
+    global_keys: bool,
+    /// Receives whether the canvas currently holds the pointer lock.
+    #[prop(optional, into)]
+    pointer_locked: Option<WriteSignal<bool>>,
+    /// Scene parameters handed to [`Renderer::set_params`] on the next frame whenever they change.
+    #[prop(optional, into)]
+    params: Option<Signal<R::Params>>,
+    /// Runs [`Renderer::fixed_update`] in steps of this many seconds, as often as the elapsed
+    /// time allows, before each [`Renderer::update`].
+    #[prop(optional, into)]
+    fixed_timestep: Option<f64>,
+    /// Freezes scene time while `true`; frames keep being drawn with a zero delta.
+    #[prop(optional, into)]
+    paused: Option<Signal<bool>>,
+    /// Multiplies the frame delta, e.g. `0.5` for slow motion. `1.0` by default.
+    #[prop(optional, into)]
+    time_scale: Option<Signal<f64>>,
+    /// Whether frames are drawn continuously (the default) or only when something changed.
+    /// Can be switched while the canvas is mounted.
+    #[prop(optional, into)]
+    render_mode: Option<Signal<RenderMode>>,
+    #[prop(optional)] _renderer: PhantomData<R>,
+) -> impl IntoView {
+    let canvas_node_ref = NodeRef::<Canvas>::new();
+
+    // filled in once the render loop is running, so signal changes can ask it for a frame
+    let redraw: Rc<RefCell<Option<RedrawHandle>>> = Rc::new(RefCell::new(None));
+    let request_redraw = {
+        let redraw = redraw.clone();
+        move || {
+            if let Some(redraw) = redraw.borrow().as_ref() {
+                redraw.request_redraw();
+            }
+        }
+    };
+
+    // switching back to continuous mode has to restart a loop that went idle
+    Effect::new({
+        let request_redraw = request_redraw.clone();
+        move |_| {
+            if let Some(render_mode) = render_mode {
+                render_mode.track();
+            }
+            request_redraw();
+        }
+    });
+
+    // the latest params are kept here and handed to the renderer at the start of the next frame
+    let pending_params: Rc<RefCell<Option<R::Params>>> = Rc::new(RefCell::new(None));
+    Effect::new({
+        let pending_params = pending_params.clone();
+        move |_| {
+            if let Some(params) = params {
+                *pending_params.borrow_mut() = Some(params.get());
+                request_redraw();
+            }
+        }
+    });
+
+    // turning pointer lock off has to release a pointer that is already locked
+    Effect::new(move |_| {
+        if pointer_lock.is_none_or(|pointer_lock| pointer_lock.get()) {
+            return;
+        }
+
+        let Some(canvas) = canvas_node_ref.get_untracked() else {
+            return;
+        };
+        if let Some(document) = canvas.owner_document() {
+            if document.pointer_lock_element().is_some_and(|element| element == **canvas) {
+                document.exit_pointer_lock();
+            }
+        }
+    });
+
+    // shared with the cleanup below, which may run before the renderer has finished initializing
+    let render_loop: Rc<RefCell<Option<RenderLoop<R>>>> = Rc::new(RefCell::new(None));
+    let disposed = Rc::new(Cell::new(false));
+    let error = RwSignal::new(None::<Error>);
+    let reporter = StatusReporter {
+        signal: status,
+        last: Rc::new(RefCell::new(None)),
+    };
+
+    reporter.report(RenderStatus::Initializing);
+
+    on_cleanup({
+        let teardown = SendWrapper::new((render_loop.clone(), disposed.clone()));
+        move || {
+            let (render_loop, disposed) = teardown.take();
+            disposed.set(true);
+            render_loop.borrow_mut().take();
+        }
+    });
+
+    canvas_node_ref.on_load(move |canvas| {
+        let canvas = Rc::new(canvas.clone());
+
+        // touches are handled as gestures, so the browser must not scroll or zoom the page for them
+        let _ = leptos::web_sys::HtmlElement::style(&canvas).set_property("touch-action", "none");
+
+        // the device limits aren't known yet, so start from the smallest maximum any backend has
+        fit_canvas(&canvas, scale_factor(), wgpu::Limits::downlevel_webgl2_defaults().max_texture_dimension_2d);
+
+        spawn_local(async move {
+            leptos::logging::log!("Spawning local thread to handle renderer");
+
+            let renderer = R::init(canvas.clone(), config.unwrap_or_default()).await;
+
+            if disposed.get() {
+                // unmounted while the device was being created
+                return;
+            }
+
+            let renderer = match renderer {
+                Ok(renderer) => renderer,
+                Err(e) => {
+                    leptos::logging::error!("Failed to initialize renderer: {e:?}");
+                    reporter.report(RenderStatus::Stopped(e.to_string()));
+                    error.set(Some(e.into()));
+                    return;
+                }
+            };
+
+            let renderer: Rc<RefCell<R>> = Rc::new(RefCell::new(renderer));
+
+            // now that the device is known the canvas may be allowed to grow past the initial clamp
+            let scale_factor = scale_factor();
+            let max_dimension = renderer.borrow().max_texture_dimension();
+            fit_canvas(&canvas, scale_factor, max_dimension);
+            renderer.borrow_mut().resize(canvas.clone(), scale_factor);
+
+            let frame_callback: FrameCallback = Rc::new(RefCell::new(None));
+            let frame_id: Rc<Cell<Option<i32>>> = Rc::new(Cell::new(None));
+
+            let window = Rc::new(leptos::web_sys::window().expect("no global window"));
+            let document = window.document().expect("no document on window");
+
+            // nothing is drawn while the canvas is scrolled out of view or the tab is hidden
+            let onscreen = Rc::new(Cell::new(true));
+            let page_visible = Rc::new(Cell::new(!document.hidden()));
+            let suspended = Rc::new(Cell::new(false));
+            // set when the loop was suspended from outside a frame, so the next one starts timing over
+            let reset_timing = Rc::new(Cell::new(false));
+            // set once an unrecoverable error ends the loop for good
+            let stopped = Rc::new(Cell::new(false));
+
+            // schedules the next frame unless one is already pending or the loop was stopped
+            let request_frame: Rc<dyn Fn()> = Rc::new({
+                let window = window.clone();
+                let frame_callback = frame_callback.clone();
+                let frame_id = frame_id.clone();
+                let stopped = stopped.clone();
+                move || {
+                    if stopped.get() || frame_id.get().is_some() {
+                        return;
+                    }
+
+                    if let Some(callback) = frame_callback.borrow().as_ref() {
+                        let id = window
+                            .request_animation_frame(callback.as_ref().unchecked_ref())
+                            .expect("Failed to request animation frame");
+                        frame_id.set(Some(id));
+                    }
+                }
+            });
+
+            let renderer_for_render = renderer.clone();
+            let canvas_for_render = canvas.clone();
+            let reporter_for_render = reporter.clone();
+
+            *frame_callback.borrow_mut() = Some(Closure::wrap(Box::new({
+                let frame_id = frame_id.clone();
+                let request_frame = request_frame.clone();
+                let onscreen = onscreen.clone();
+                let page_visible = page_visible.clone();
+                let suspended = suspended.clone();
+                let reset_timing = reset_timing.clone();
+                let stopped = stopped.clone();
+                let navigator = window.navigator();
+                let mut stats = StatsPublisher::new(stats, &window);
+                let mut clock = FrameClock::new();
+                // `timestamp` is the `DOMHighResTimeStamp` rAF passes in, in milliseconds
+                move |timestamp: f64| {
+                    // this frame is running, so a redraw requested from here on needs a new one
+                    frame_id.set(None);
+
+                    if reset_timing.take() {
+                        clock.reset();
+                        stats.reset();
+                    }
+
+                    if !onscreen.get() || !page_visible.get() {
+                        // no next frame is requested; `resume` below restarts the loop
+                        suspended.set(true);
+                        clock.reset();
+                        stats.reset();
+                        reporter_for_render.report(RenderStatus::Suspended);
+                        return;
+                    }
+
+                    if let Some(params) = pending_params.take() {
+                        renderer_for_render.borrow_mut().set_params(params);
+                    }
+
+                    let gamepads = poll_gamepads(&navigator);
+                    renderer_for_render.borrow_mut().handle_gamepads(&gamepads);
+
+                    // Call the render function
+                    // leptos::logging::log!("redraw");
+                    let paused = paused.is_some_and(|paused| paused.get_untracked());
+                    let time_scale = time_scale.map_or(1.0, |time_scale| time_scale.get_untracked());
+                    let time = clock.tick(timestamp, paused, time_scale);
+
+                    match fixed_timestep {
+                        Some(step) => {
+                            for step_time in clock.fixed_steps(step) {
+                                renderer_for_render.borrow_mut().fixed_update(step_time);
+                            }
+                        }
+                        None => clock.clear_accumulator(),
+                    }
+
+                    renderer_for_render.borrow_mut().update(time);
+
+                    let failure = renderer_for_render.borrow_mut().take_error();
+                    if let Some(e) = failure {
+                        leptos::logging::error!("Stopping render loop: {e}");
+                        stopped.set(true);
+                        reporter_for_render.report(RenderStatus::Stopped(e.to_string()));
+                        error.set(Some(e.into()));
+                        return;
+                    }
+
+                    let result = renderer_for_render.borrow_mut().render();
+                    stats.frame(&*renderer_for_render.borrow(), &canvas_for_render);
+
+                    match result {
+                        Ok(()) => reporter_for_render.report(RenderStatus::Running),
+                        Err(e) => match Recovery::from(&e) {
+                            // nothing was presented, so even an on-demand loop needs another frame
+                            Recovery::Reconfigure => {
+                                renderer_for_render.borrow_mut().reconfigure(canvas_for_render.clone());
+                                reporter_for_render.report(RenderStatus::Reconfigured);
+                                request_frame();
+                            }
+                            Recovery::Skip => {
+                                reporter_for_render.report(RenderStatus::SkippedFrame);
+                                request_frame();
+                            }
+                            Recovery::Stop => {
+                                leptos::logging::error!("Stopping render loop: {e}");
+                                stopped.set(true);
+                                reporter_for_render.report(RenderStatus::Stopped(e.to_string()));
+                                error.set(Some(e.into()));
+                                return;
+                            }
+                        },
+                    }
+
+                    // Schedule next frame
+                    let continuous = render_mode.map_or(RenderMode::Continuous, |mode| mode.get_untracked())
+                        == RenderMode::Continuous;
+                    if continuous || renderer_for_render.borrow().wants_redraw() {
+                        request_frame();
+                    } else if frame_id.get().is_none() {
+                        // going idle; the next frame starts timing over instead of jumping ahead
+                        clock.reset();
+                        stats.reset();
+                    }
+                }
+            }) as Box<dyn FnMut(f64)>));
+
+            let redraw_handle = RedrawHandle::new({
+                let request_frame = request_frame.clone();
+                move || request_frame()
+            });
+            renderer.borrow_mut().set_redraw_handle(redraw_handle.clone());
+            *redraw.borrow_mut() = Some(redraw_handle.clone());
+
+            request_frame();
+
+            // hidden tabs get no animation frames at all, so the loop can't notice it on its own
+            let suspend: Rc<dyn Fn()> = Rc::new({
+                let window = window.clone();
+                let frame_id = frame_id.clone();
+                let suspended = suspended.clone();
+                move || {
+                    if stopped.get() || suspended.get() {
+                        return;
+                    }
+
+                    if let Some(id) = frame_id.take() {
+                        let _ = window.cancel_animation_frame(id);
+                    }
+                    suspended.set(true);
+                    reset_timing.set(true);
+                    reporter.report(RenderStatus::Suspended);
+                }
+            });
+
+            // restarts a suspended loop once the canvas is both onscreen and in a visible tab;
+            // a loop stopped by an error is never suspended, so it stays stopped
+            let resume: Rc<dyn Fn()> = Rc::new({
+                let onscreen = onscreen.clone();
+                let page_visible = page_visible.clone();
+                move || {
+                    if !suspended.get() || !onscreen.get() || !page_visible.get() {
+                        return;
+                    }
+
+                    suspended.set(false);
+                    request_frame();
+                }
+            });
+
+            let visibility_changed: Rc<dyn Fn()> = Rc::new({
+                let page_visible = page_visible.clone();
+                let resume = resume.clone();
+                move || {
+                    if page_visible.get() {
+                        resume();
+                    } else {
+                        suspend();
+                    }
+                }
+            });
+
+            // a focused canvas is the only one that should react to the keyboard
+            let key_target: &leptos::web_sys::EventTarget = if global_keys { &window } else { &canvas };
+
+            // the actions held down right now, shared by the key listeners
+            let active_actions = Rc::new(RefCell::new(ActiveActions::default()));
+
+            let mut listeners = vec![
+                keydown_callback(key_target, renderer.clone(), active_actions.clone()),
+                keyup_callback(key_target, renderer.clone(), active_actions.clone()),
+                blur_callback(key_target, renderer.clone(), active_actions),
+                gamepad_connection_callback(&window, renderer.clone(), "gamepadconnected"),
+                gamepad_connection_callback(&window, renderer.clone(), "gamepaddisconnected"),
+                pointerdown_callback(&canvas, renderer.clone()),
+                pointerup_callback(&canvas, renderer.clone()),
+                pointercancel_callback(&canvas, renderer.clone()),
+                pointermove_callback(&canvas, renderer.clone()),
+                wheel_callback(&canvas, renderer.clone()),
+                visibilitychange_callback(&document, page_visible, visibility_changed),
+            ];
+            if let Some(pointer_lock) = pointer_lock {
+                listeners.extend([
+                    request_pointer_lock_callback(&canvas, pointer_lock),
+                    pointerlockchange_callback(&canvas, renderer.clone(), pointer_locked),
+                    exit_pointer_lock_callback(&window, &canvas),
+                    redraw_callback(&document, "pointerlockchange", redraw_handle.clone()),
+                ]);
+            }
+
+            // in on-demand mode input is what brings the scene up to date
+            for event in ["keydown", "keyup", "blur"] {
+                listeners.push(redraw_callback(key_target, event, redraw_handle.clone()));
+            }
+            for event in ["pointerdown", "pointerup", "pointercancel", "pointermove", "wheel"] {
+                listeners.push(redraw_callback(&canvas, event, redraw_handle.clone()));
+            }
+            for event in ["gamepadconnected", "gamepaddisconnected"] {
+                listeners.push(redraw_callback(&window, event, redraw_handle.clone()));
+            }
+
+            let observers = vec![
+                resize_observer_callback(renderer.clone(), canvas.clone(), redraw_handle),
+                intersection_observer_callback(&canvas, onscreen, resume),
+            ];
+
+            *render_loop.borrow_mut() = Some(RenderLoop {
+                window,
+                frame_id,
+                frame_callback,
+                listeners,
+                observers,
+                renderer,
+            });
+        });
+    });
+
+    view! {
+        <canvas
+            node_ref=canvas_node_ref
+            // focusable, so it can receive keys by being clicked or tabbed to
+            tabindex="0"
+            class=class
+            style=style
+        ></canvas>
+        {move || error.get().map_or(Ok(()), Err)}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::convert::Infallible;
+    use std::rc::Rc;
+
+    use leptos::prelude::*;
+    use leptos::wasm_bindgen::JsCast;
+    use leptos::web_sys::HtmlCanvasElement;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::WgpuCanvas;
+    use crate::utils::helpers::testing::sleep;
+    use crate::utils::renderer::Renderer;
+    use crate::utils::types::frame_time::FrameTime;
+    use crate::utils::types::render_status::RenderStatus;
+
+    thread_local! {
+        static LIVE_RENDERERS: Cell<usize> = const { Cell::new(0) };
+        static FRAMES_RENDERED: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// Draws nothing, so the loop runs without a GPU; counts how many instances are alive.
+    struct CountingRenderer;
+
+    impl Drop for CountingRenderer {
+        fn drop(&mut self) {
+            LIVE_RENDERERS.set(LIVE_RENDERERS.get() - 1);
+        }
+    }
+
+    impl Renderer for CountingRenderer {
+        type Error = Infallible;
+        type Config = ();
+        type Params = ();
+
+        async fn init(_canvas: Rc<HtmlCanvasElement>, _config: ()) -> Result<Self, Infallible> {
+            LIVE_RENDERERS.set(LIVE_RENDERERS.get() + 1);
+            Ok(Self)
+        }
+
+        fn update(&mut self, _time: FrameTime) {}
+
+        fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+            FRAMES_RENDERED.set(FRAMES_RENDERED.get() + 1);
+            Ok(())
+        }
+
+        fn resize(&mut self, _canvas: Rc<HtmlCanvasElement>, _scale_factor: f64) {}
+    }
+
+    #[wasm_bindgen_test]
+    async fn unmounting_releases_the_render_loop() {
+        let document = leptos::web_sys::window().unwrap().document().unwrap();
+
+        for round in 0..50 {
+            let container: leptos::web_sys::HtmlElement = document.create_element("div").unwrap().unchecked_into();
+            document.body().unwrap().append_child(&container).unwrap();
+
+            let (status, set_status) = signal(RenderStatus::Initializing);
+            let handle = leptos::mount::mount_to(container.clone(), move || {
+                view! { <WgpuCanvas<CountingRenderer> status=set_status global_keys=true /> }
+            });
+
+            // wait for the renderer to initialize and the first frame to be drawn
+            for _ in 0..100 {
+                if status.get_untracked() != RenderStatus::Initializing {
+                    break;
+                }
+                sleep(10).await;
+            }
+            assert_eq!(status.get_untracked(), RenderStatus::Running, "round {round}");
+            assert_eq!(LIVE_RENDERERS.get(), 1, "round {round}");
+
+            drop(handle);
+            container.remove();
+
+            // every listener, observer and the frame callback held a clone of the renderer
+            assert_eq!(LIVE_RENDERERS.get(), 0, "the renderer outlived its canvas in round {round}");
+
+            // a removed keydown listener would have to borrow the freed renderer
+            let event = leptos::web_sys::KeyboardEvent::new("keydown").unwrap();
+            leptos::web_sys::window().unwrap().dispatch_event(&event).unwrap();
+
+            // and a cancelled frame would keep drawing
+            let frames = FRAMES_RENDERED.get();
+            sleep(50).await;
+            assert_eq!(FRAMES_RENDERED.get(), frames, "frames kept being drawn after round {round}");
+        }
+    }
+}
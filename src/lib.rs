@@ -2,11 +2,47 @@ use leptos::prelude::*;
 use leptos_meta::*;
 use leptos_router::{components::*, path};
 
+// the canvas only works in a browser, so the tests run in one too
+#[cfg(test)]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
 // Modules
 mod components;
 mod pages;
 mod utils;
 
+// Public API
+pub use crate::components::stats_overlay::StatsOverlay;
+pub use crate::components::wgpu_canvas::WgpuCanvas;
+pub use crate::utils::controllers::{
+    fly::FlyController, orbit::OrbitController, pan_zoom::PanZoomController, CameraController,
+};
+pub use crate::utils::gpu::GpuConfig;
+pub use crate::utils::renderer::Renderer;
+pub use crate::utils::state::State;
+pub use crate::utils::types::action_map::{ActionEvent, ActionMap, KeyChord};
+pub use crate::utils::types::action_map_error::ActionMapError;
+pub use crate::utils::types::buffers::{PolygonGeometry, Vertex};
+pub use crate::utils::types::camera::{Camera, CameraMode};
+pub use crate::utils::types::frame_time::FrameTime;
+pub use crate::utils::types::gamepad::{
+    GamepadAxis, GamepadButton, GamepadButtonState, GamepadConnection, GamepadSnapshot, GamepadState,
+};
+pub use crate::utils::types::gesture::{Gesture, GestureConfig, GestureRecognizer};
+pub use crate::utils::types::input_state::InputState;
+pub use crate::utils::types::key_event::{KeyEvent, Modifiers};
+pub use crate::utils::types::keycode::KeyCode;
+pub use crate::utils::types::pointer_event::{PointerButton, PointerEvent, PointerKind, PointerPosition, WheelEvent};
+pub use crate::utils::types::render_mode::{RedrawHandle, RenderMode};
+pub use crate::utils::types::render_stats::RenderStats;
+pub use crate::utils::types::render_status::RenderStatus;
+pub use crate::utils::types::scene_update::SceneUpdate;
+pub use crate::utils::types::size::{LogicalSize, PhysicalPosition, PhysicalSize};
+pub use crate::utils::types::state_error::StateError;
+// the wgpu this crate is built against, for `vertex_attributes!` and for renderers that
+// don't depend on it directly
+pub use wgpu;
+
 // Top-Level pages
 use crate::pages::home::Home;
 
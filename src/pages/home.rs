@@ -1,13 +1,70 @@
 use leptos::prelude::*;
 
-use crate::components::window::Window;
+use crate::components::stats_overlay::StatsOverlay;
+use crate::components::wgpu_canvas::WgpuCanvas;
+use crate::utils::state::State;
+use crate::utils::types::render_mode::RenderMode;
+use crate::utils::types::render_stats::RenderStats;
+use crate::utils::types::scene_update::SceneUpdate;
+
+/// Parses an `<input type="color">` value such as `#1a334d` into linear RGB, which is what
+/// the sRGB surface expects its colors in.
+fn hex_to_linear(hex: &str) -> Option<[f32; 3]> {
+    let hex = hex.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+
+    let channel = |i: usize| -> Option<f32> {
+        let srgb = u8::from_str_radix(hex.get(i..i + 2)?, 16).ok()? as f32 / 255.0;
+        Some(if srgb <= 0.04045 {
+            srgb / 12.92
+        } else {
+            ((srgb + 0.055) / 1.055).powf(2.4)
+        })
+    };
+
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
 
 /// Default Home Page
 #[allow(non_snake_case)]
 #[component]
 pub fn Home() -> impl IntoView {
+    // bumping this remounts the canvas, which re-runs the renderer initialization
+    let (attempt, set_attempt) = signal(0);
+
+    let (clear_hex, set_clear_hex) = signal("#1a334d".to_string());
+    let (vertex_hex, set_vertex_hex) = signal("#ff0000".to_string());
+    let (sides, set_sides) = signal(5u16);
+    let (radius, set_radius) = signal(0.5f32);
+    let (stats, set_stats) = signal(RenderStats::default());
+    let (on_demand, set_on_demand) = signal(false);
+    let render_mode = Signal::derive(move || {
+        if on_demand.get() {
+            RenderMode::OnDemand
+        } else {
+            RenderMode::Continuous
+        }
+    });
+
+    let params = Signal::derive(move || {
+        let [r, g, b] = hex_to_linear(&clear_hex.get()).unwrap_or([0.0, 0.0, 0.0]);
+        SceneUpdate {
+            clear_color: Some(wgpu::Color {
+                r: r as f64,
+                g: g as f64,
+                b: b as f64,
+                a: 1.0,
+            }),
+            polygon_sides: Some(sides.get()),
+            polygon_radius: Some(radius.get()),
+            vertex_color: Some(hex_to_linear(&vertex_hex.get()).unwrap_or([1.0, 0.0, 0.0])),
+        }
+    });
+
     view! {
-        <ErrorBoundary fallback=|errors| {
+        <ErrorBoundary fallback=move |errors| {
             view! {
                 <h1>"Uh oh! Something went wrong!"</h1>
 
@@ -23,11 +80,82 @@ pub fn Home() -> impl IntoView {
                     }}
 
                 </ul>
+
+                <button on:click=move |_| set_attempt.update(|n| *n += 1)>"Retry"</button>
             }
         }>
 
             <div class="container">
-                <Window />
+                <form class="settings" on:submit=|ev| ev.prevent_default()>
+                    <label>
+                        "Background "
+                        <input
+                            type="color"
+                            prop:value=clear_hex
+                            on:input=move |ev| set_clear_hex.set(event_target_value(&ev))
+                        />
+                    </label>
+                    <label>
+                        "Color "
+                        <input
+                            type="color"
+                            prop:value=vertex_hex
+                            on:input=move |ev| set_vertex_hex.set(event_target_value(&ev))
+                        />
+                    </label>
+                    <label>
+                        {move || format!("Sides: {}", sides.get())}
+                        <input
+                            type="range"
+                            min="3"
+                            max="64"
+                            prop:value=move || sides.get().to_string()
+                            on:input=move |ev| {
+                                if let Ok(value) = event_target_value(&ev).parse() {
+                                    set_sides.set(value);
+                                }
+                            }
+                        />
+                    </label>
+                    <label>
+                        {move || format!("Radius: {:.2}", radius.get())}
+                        <input
+                            type="range"
+                            min="0.05"
+                            max="1"
+                            step="0.05"
+                            prop:value=move || radius.get().to_string()
+                            on:input=move |ev| {
+                                if let Ok(value) = event_target_value(&ev).parse() {
+                                    set_radius.set(value);
+                                }
+                            }
+                        />
+                    </label>
+                    <label>
+                        <span>
+                            <input
+                                type="checkbox"
+                                prop:checked=on_demand
+                                on:change=move |ev| set_on_demand.set(event_target_checked(&ev))
+                            />
+                            " Render on demand"
+                        </span>
+                    </label>
+                </form>
+
+                {move || {
+                    attempt.track();
+                    view! {
+                        <WgpuCanvas<State>
+                            params=params
+                            stats=set_stats
+                            render_mode=render_mode
+                        />
+                    }
+                }}
+
+                <StatsOverlay stats=stats />
             </div>
         </ErrorBoundary>
     }
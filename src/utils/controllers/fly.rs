@@ -0,0 +1,77 @@
+use std::f32::consts::FRAC_PI_2;
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::utils::controllers::CameraController;
+use crate::utils::types::{camera::Camera, gamepad::GamepadAxis, input_state::InputState, keycode::KeyCode};
+
+/// First person movement: W/S to move forward and back, A/D to strafe, Space/C to rise and
+/// sink, and the arrow keys, a one finger drag or the mouse while the pointer is locked to
+/// look around. On a gamepad the left stick moves, the right stick looks around and the
+/// triggers sink and rise.
+#[derive(Debug, Clone)]
+pub struct FlyController {
+    /// World units per second.
+    pub move_speed: f32,
+    /// Radians per second.
+    pub look_speed: f32,
+    /// Radians per NDC unit of touch drag.
+    pub drag_speed: f32,
+    /// Radians per canvas pixel of locked mouse motion.
+    pub mouse_sensitivity: f32,
+}
+
+impl Default for FlyController {
+    fn default() -> Self {
+        Self {
+            move_speed: 2.0,
+            look_speed: 1.5,
+            drag_speed: 1.0,
+            mouse_sensitivity: 0.002,
+        }
+    }
+}
+
+impl CameraController for FlyController {
+    fn update_camera(&mut self, camera: &mut Camera, input: &InputState, dt: f32) {
+        let forward_input = input.axis(&[KeyCode::KeyCodeS], &[KeyCode::KeyCodeW]);
+        let right_input = input.axis(&[KeyCode::KeyCodeA], &[KeyCode::KeyCodeD]);
+        let up_input = input.axis(&[KeyCode::KeyCodeC], &[KeyCode::KeyCodeSpace]);
+        let yaw_input = input.axis(&[KeyCode::KeyCodeArrowRight], &[KeyCode::KeyCodeArrowLeft]);
+        let pitch_input = input.axis(&[KeyCode::KeyCodeArrowDown], &[KeyCode::KeyCodeArrowUp]);
+        let [drag_x, drag_y] = input.touch_drag(1);
+
+        let forward_input = (forward_input + input.gamepad_axis(GamepadAxis::LeftStickY)).clamp(-1.0, 1.0);
+        let right_input = (right_input + input.gamepad_axis(GamepadAxis::LeftStickX)).clamp(-1.0, 1.0);
+        let up_input = (up_input + input.gamepad_axis(GamepadAxis::RightTrigger)
+            - input.gamepad_axis(GamepadAxis::LeftTrigger))
+        .clamp(-1.0, 1.0);
+        let yaw_input = (yaw_input - input.gamepad_axis(GamepadAxis::RightStickX)).clamp(-1.0, 1.0);
+        let pitch_input = (pitch_input + input.gamepad_axis(GamepadAxis::RightStickY)).clamp(-1.0, 1.0);
+        let [motion_x, motion_y] = if input.is_pointer_locked() {
+            input.pointer_motion().map(|motion| motion as f32 * self.mouse_sensitivity)
+        } else {
+            [0.0, 0.0]
+        };
+
+        let view = camera.target - camera.eye;
+        let distance = view.magnitude();
+        if distance <= f32::EPSILON {
+            return;
+        }
+
+        // yaw is measured around +y from -z, the default viewing direction
+        let forward = view / distance;
+        // like grabbing the world: dragging right turns the view left
+        let yaw = (-forward.x).atan2(-forward.z) + yaw_input * self.look_speed * dt + drag_x * self.drag_speed - motion_x;
+        let pitch = (forward.y.asin() + pitch_input * self.look_speed * dt - drag_y * self.drag_speed - motion_y)
+            .clamp(-FRAC_PI_2 + 0.01, FRAC_PI_2 - 0.01);
+        let forward = Vector3::new(-pitch.cos() * yaw.sin(), pitch.sin(), -pitch.cos() * yaw.cos());
+
+        let right = forward.cross(camera.up).normalize();
+        let movement = forward * forward_input + right * right_input + camera.up.normalize() * up_input;
+
+        camera.eye += movement * self.move_speed * dt;
+        camera.target = camera.eye + forward * distance;
+    }
+}
@@ -0,0 +1,169 @@
+pub mod fly;
+pub mod orbit;
+pub mod pan_zoom;
+
+use crate::utils::types::{camera::Camera, input_state::InputState};
+
+/// Moves a [`Camera`] from the keys held in an [`InputState`].
+///
+/// Speeds are in units per second and scaled by `dt`, so movement does not depend on the frame rate.
+pub trait CameraController {
+    /// Advances `camera` by `dt` seconds according to `input`.
+    fn update_camera(&mut self, camera: &mut Camera, input: &InputState, dt: f32);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_4;
+
+    use cgmath::{EuclideanSpace, InnerSpace, MetricSpace, Point3};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::{fly::FlyController, orbit::OrbitController, pan_zoom::PanZoomController, CameraController};
+    use crate::utils::helpers::testing::touch;
+    use crate::utils::types::{camera::Camera, input_state::InputState, keycode::KeyCode};
+
+    const DT: f32 = 1.0 / 30.0;
+
+    fn holding(keys: &[KeyCode]) -> InputState {
+        let mut input = InputState::default();
+        for &key in keys {
+            input.press(key, false);
+        }
+        input
+    }
+
+    fn assert_points_close(a: Point3<f32>, b: Point3<f32>) {
+        assert!(a.distance(b) < 1e-5, "{a:?} != {b:?}");
+    }
+
+    /// The camera after one step of `DT`, and after two steps of half of it.
+    fn step(controller: &mut impl CameraController, camera: Camera, keys: &[KeyCode]) -> (Camera, Camera) {
+        let input = holding(keys);
+
+        let mut once = camera;
+        controller.update_camera(&mut once, &input, DT);
+
+        let mut twice = camera;
+        controller.update_camera(&mut twice, &input, DT / 2.0);
+        controller.update_camera(&mut twice, &input, DT / 2.0);
+
+        (once, twice)
+    }
+
+    #[wasm_bindgen_test]
+    fn orbit_turns_tilts_and_zooms_around_the_target() {
+        let mut controller = OrbitController::default();
+        let camera = Camera::default();
+        let distance = |camera: &Camera| camera.eye.distance(camera.target);
+
+        let (turned, halves) = step(&mut controller, camera, &[KeyCode::KeyCodeD]);
+        assert!(turned.eye.x > 0.0, "D should circle to the right, got {:?}", turned.eye);
+        assert!((distance(&turned) - distance(&camera)).abs() < 1e-5);
+        assert_eq!(turned.target, camera.target);
+        assert_points_close(turned.eye, halves.eye);
+
+        let (tilted, halves) = step(&mut controller, camera, &[KeyCode::KeyCodeW]);
+        assert!(tilted.eye.y > 0.0, "W should tilt up, got {:?}", tilted.eye);
+        assert_points_close(tilted.eye, halves.eye);
+
+        let (zoomed, halves) = step(&mut controller, camera, &[KeyCode::KeyCodeQ]);
+        assert!(distance(&zoomed) < distance(&camera), "Q should move closer");
+        assert_points_close(zoomed.eye, halves.eye);
+    }
+
+    #[wasm_bindgen_test]
+    fn fly_moves_along_and_turns_the_view() {
+        let mut controller = FlyController::default();
+        let camera = Camera::default();
+        let forward = |camera: &Camera| (camera.target - camera.eye).normalize();
+
+        let (moved, halves) = step(&mut controller, camera, &[KeyCode::KeyCodeW]);
+        assert!(moved.eye.z < camera.eye.z, "W should move towards -z, got {:?}", moved.eye);
+        assert!((moved.eye.z - (camera.eye.z - controller.move_speed * DT)).abs() < 1e-5);
+        assert_points_close(moved.eye, halves.eye);
+        assert_points_close(moved.target, halves.target);
+
+        let (strafed, halves) = step(&mut controller, camera, &[KeyCode::KeyCodeD]);
+        assert!(strafed.eye.x > 0.0, "D should strafe right, got {:?}", strafed.eye);
+        assert_points_close(strafed.eye, halves.eye);
+
+        let (risen, halves) = step(&mut controller, camera, &[KeyCode::KeyCodeSpace]);
+        assert!(risen.eye.y > 0.0, "Space should rise, got {:?}", risen.eye);
+        assert_points_close(risen.eye, halves.eye);
+
+        let (turned, halves) = step(&mut controller, camera, &[KeyCode::KeyCodeArrowLeft]);
+        assert_eq!(turned.eye, camera.eye);
+        assert!(forward(&turned).x < 0.0, "Left should turn left, got {:?}", forward(&turned));
+        assert_points_close(turned.target, halves.target);
+
+        let (looked_up, halves) = step(&mut controller, camera, &[KeyCode::KeyCodeArrowUp]);
+        assert!(forward(&looked_up).y > 0.0, "Up should look up, got {:?}", forward(&looked_up));
+        assert_points_close(looked_up.target, halves.target);
+    }
+
+    /// Two fingers on either side of the canvas center, turned 45 degrees counter-clockwise
+    /// around it, so they neither drag nor pinch overall.
+    fn twisted() -> InputState {
+        let mut input = InputState::default();
+        input.pointer_down(&touch(1, 50.0, 100.0, 0.0));
+        input.pointer_down(&touch(2, 150.0, 100.0, 0.0));
+
+        let offset = 50.0 * std::f64::consts::FRAC_1_SQRT_2;
+        input.pointer_move(&touch(1, 100.0 - offset, 100.0 + offset, 16.0));
+        input.pointer_move(&touch(2, 100.0 + offset, 100.0 - offset, 16.0));
+
+        assert!((input.touch_rotation() - FRAC_PI_4).abs() < 1e-5);
+        input
+    }
+
+    #[wasm_bindgen_test]
+    fn orbit_twist_turns_the_scene_with_the_fingers() {
+        let mut camera = Camera::default();
+        OrbitController::default().update_camera(&mut camera, &twisted(), DT);
+
+        // the scene turns counter-clockwise seen from above, so the camera goes the other way
+        assert!(camera.eye.x < 0.0, "got {:?}", camera.eye);
+        assert!((camera.eye.distance(camera.target) - 1.0).abs() < 1e-5);
+    }
+
+    #[wasm_bindgen_test]
+    fn pan_zoom_twist_rolls_the_view_with_the_fingers() {
+        let mut camera = Camera::default();
+        PanZoomController::default().update_camera(&mut camera, &twisted(), DT);
+
+        // the world x axis now points where the fingers turned it on screen
+        let matrix = camera.build_view_projection_matrix();
+        let ndc = |point: Point3<f32>| {
+            let clip = matrix * point.to_homogeneous();
+            clip.truncate() / clip.w
+        };
+        let axis = ndc(Point3::new(1.0, 0.0, 0.0)) - ndc(Point3::origin());
+        assert!((axis.y.atan2(axis.x) - FRAC_PI_4).abs() < 1e-4, "got {axis:?}");
+        assert_points_close(camera.target, Point3::origin());
+
+        // panning right follows the turned screen instead of the world x axis
+        let (panned, _) = step(&mut PanZoomController::default(), camera, &[KeyCode::KeyCodeD]);
+        let moved = ndc(panned.target);
+        assert!(moved.x > 0.0 && moved.y.abs() < 1e-5, "got {moved:?}");
+    }
+
+    #[wasm_bindgen_test]
+    fn pan_zoom_pans_and_zooms_the_view() {
+        let mut controller = PanZoomController::default();
+        let camera = Camera::default();
+
+        let (panned, halves) = step(&mut controller, camera, &[KeyCode::KeyCodeD, KeyCode::KeyCodeW]);
+        assert!(panned.eye.x > 0.0 && panned.eye.y > 0.0, "D and W should pan right and up, got {:?}", panned.eye);
+        // the view direction stays the same
+        assert_eq!(panned.target - panned.eye, camera.target - camera.eye);
+        assert_points_close(panned.eye, halves.eye);
+
+        let (zoomed, halves) = step(&mut controller, camera, &[KeyCode::KeyCodeQ]);
+        assert!(zoomed.ortho_size < camera.ortho_size, "Q should zoom in");
+        assert!((zoomed.ortho_size - halves.ortho_size).abs() < 1e-6);
+
+        let (zoomed_out, _) = step(&mut controller, camera, &[KeyCode::KeyCodeE]);
+        assert!(zoomed_out.ortho_size > camera.ortho_size, "E should zoom out");
+    }
+}
@@ -0,0 +1,73 @@
+use std::f32::consts::FRAC_PI_2;
+
+use cgmath::{InnerSpace, Vector3};
+
+use crate::utils::controllers::CameraController;
+use crate::utils::types::{camera::Camera, gamepad::GamepadAxis, input_state::InputState, keycode::KeyCode};
+
+/// Circles the camera around its target: A/D, Left/Right, a one finger drag or twisting two
+/// fingers to turn, W/S, Up/Down or the same drag to tilt, Q/E or pinching to move closer or
+/// further away. On a gamepad the left stick turns and tilts and the right stick moves closer
+/// or further away.
+#[derive(Debug, Clone)]
+pub struct OrbitController {
+    /// Radians per second.
+    pub rotate_speed: f32,
+    /// Fraction of the distance to the target per second.
+    pub zoom_speed: f32,
+    /// Radians per NDC unit of touch drag, so dragging across the whole canvas turns by `2 * drag_speed`.
+    pub drag_speed: f32,
+    pub min_distance: f32,
+    pub max_distance: f32,
+}
+
+impl Default for OrbitController {
+    fn default() -> Self {
+        Self {
+            rotate_speed: 1.5,
+            zoom_speed: 1.0,
+            drag_speed: std::f32::consts::PI / 2.0,
+            min_distance: 0.1,
+            max_distance: 100.0,
+        }
+    }
+}
+
+impl CameraController for OrbitController {
+    fn update_camera(&mut self, camera: &mut Camera, input: &InputState, dt: f32) {
+        let yaw_input = input.axis(
+            &[KeyCode::KeyCodeA, KeyCode::KeyCodeArrowLeft],
+            &[KeyCode::KeyCodeD, KeyCode::KeyCodeArrowRight],
+        );
+        let pitch_input = input.axis(
+            &[KeyCode::KeyCodeS, KeyCode::KeyCodeArrowDown],
+            &[KeyCode::KeyCodeW, KeyCode::KeyCodeArrowUp],
+        );
+        let zoom_input = input.axis(&[KeyCode::KeyCodeE], &[KeyCode::KeyCodeQ]);
+
+        let yaw_input = (yaw_input + input.gamepad_axis(GamepadAxis::LeftStickX)).clamp(-1.0, 1.0);
+        let pitch_input = (pitch_input + input.gamepad_axis(GamepadAxis::LeftStickY)).clamp(-1.0, 1.0);
+        let zoom_input = (zoom_input + input.gamepad_axis(GamepadAxis::RightStickY)).clamp(-1.0, 1.0);
+        let [drag_x, drag_y] = input.touch_drag(1);
+
+        let offset = camera.eye - camera.target;
+        let distance = offset.magnitude();
+        if distance <= f32::EPSILON {
+            return;
+        }
+
+        // spherical coordinates around the target, with the pitch kept short of the poles
+        // so `up` never lines up with the view direction
+        // dragging or twisting moves the scene with the fingers, so the camera turns the other way
+        let yaw = offset.x.atan2(offset.z) + yaw_input * self.rotate_speed * dt
+            - drag_x * self.drag_speed
+            - input.touch_rotation();
+        let pitch = ((offset.y / distance).asin() + pitch_input * self.rotate_speed * dt - drag_y * self.drag_speed)
+            .clamp(-FRAC_PI_2 + 0.01, FRAC_PI_2 - 0.01);
+        let distance = (distance * (-zoom_input * self.zoom_speed * dt).exp() / input.pinch_scale())
+            .clamp(self.min_distance, self.max_distance);
+
+        camera.eye = camera.target
+            + Vector3::new(pitch.cos() * yaw.sin(), pitch.sin(), pitch.cos() * yaw.cos()) * distance;
+    }
+}
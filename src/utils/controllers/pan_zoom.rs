@@ -0,0 +1,87 @@
+use cgmath::{InnerSpace, Quaternion, Rad, Rotation3};
+
+use crate::utils::controllers::CameraController;
+use crate::utils::types::{
+    camera::Camera, gamepad::GamepadAxis, input_state::InputState, keycode::KeyCode, pointer_event::PointerButton,
+};
+
+/// 2D navigation for orthographic scenes: WASD, the arrow keys or dragging with the primary
+/// button or one or two fingers to pan, Q/E, the wheel or pinching to zoom in and out, and
+/// twisting two fingers to turn the view. On a gamepad the left stick pans and the triggers zoom.
+#[derive(Debug, Clone)]
+pub struct PanZoomController {
+    /// Fractions of the visible half-height per second, so panning feels the same at any zoom.
+    pub pan_speed: f32,
+    /// How quickly [`Camera::ortho_size`] shrinks or grows, per second.
+    pub zoom_speed: f32,
+    /// How much one pixel of wheel scrolling zooms, as a fraction of [`Camera::ortho_size`].
+    pub wheel_zoom_speed: f32,
+    pub min_ortho_size: f32,
+    pub max_ortho_size: f32,
+}
+
+impl Default for PanZoomController {
+    fn default() -> Self {
+        Self {
+            pan_speed: 1.0,
+            zoom_speed: 1.0,
+            wheel_zoom_speed: 0.001,
+            min_ortho_size: 0.01,
+            max_ortho_size: 100.0,
+        }
+    }
+}
+
+impl CameraController for PanZoomController {
+    fn update_camera(&mut self, camera: &mut Camera, input: &InputState, dt: f32) {
+        let x_input = input.axis(
+            &[KeyCode::KeyCodeA, KeyCode::KeyCodeArrowLeft],
+            &[KeyCode::KeyCodeD, KeyCode::KeyCodeArrowRight],
+        );
+        let y_input = input.axis(
+            &[KeyCode::KeyCodeS, KeyCode::KeyCodeArrowDown],
+            &[KeyCode::KeyCodeW, KeyCode::KeyCodeArrowUp],
+        );
+        let zoom_input = input.axis(&[KeyCode::KeyCodeE], &[KeyCode::KeyCodeQ]);
+
+        let x_input = (x_input + input.gamepad_axis(GamepadAxis::LeftStickX)).clamp(-1.0, 1.0);
+        let y_input = (y_input + input.gamepad_axis(GamepadAxis::LeftStickY)).clamp(-1.0, 1.0);
+        let zoom_input = (zoom_input + input.gamepad_axis(GamepadAxis::RightTrigger)
+            - input.gamepad_axis(GamepadAxis::LeftTrigger))
+        .clamp(-1.0, 1.0);
+
+        let forward = (camera.target - camera.eye).normalize();
+
+        // the scene turns with the fingers, so the camera rolls the other way around the view axis
+        let rotation = input.touch_rotation();
+        if rotation != 0.0 {
+            camera.up = Quaternion::from_axis_angle(forward, Rad(rotation)) * camera.up;
+        }
+
+        // the screen's axes in world space, which only line up with x and y until the view is turned
+        let screen_right = forward.cross(camera.up).normalize();
+        let screen_up = screen_right.cross(forward);
+
+        // eye and target move together, so the view direction never changes
+        let pan = (screen_right * x_input + screen_up * y_input) * self.pan_speed * camera.ortho_size * dt;
+        camera.eye += pan;
+        camera.target += pan;
+
+        // dragging keeps the grabbed point under the pointer; NDC spans twice the half extents
+        let (_, right, _, top) = camera.ortho_bounds();
+        let mut drags = vec![input.touch_drag(1), input.touch_drag(2)];
+        if input.is_button_pressed(PointerButton::Primary) {
+            drags.push(input.pointer_ndc_delta());
+        }
+        for [dx, dy] in drags {
+            let drag = screen_right * (-dx * right) + screen_up * (-dy * top);
+            camera.eye += drag;
+            camera.target += drag;
+        }
+
+        let zoom = zoom_input * self.zoom_speed * dt - input.wheel_delta()[1] as f32 * self.wheel_zoom_speed
+            + input.pinch_scale().ln();
+        camera.ortho_size = (camera.ortho_size * (-zoom).exp())
+            .clamp(self.min_ortho_size, self.max_ortho_size);
+    }
+}
@@ -0,0 +1,332 @@
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use leptos::wasm_bindgen::JsCast;
+
+use crate::utils::state::Scene;
+use crate::utils::types::camera::Camera;
+use wgpu::util::DeviceExt;
+
+use crate::utils::types::buffers::{Vertex, polygon_vertex::PolygonVertex, camera_uniform::CameraUniform};
+use crate::utils::types::state_error::StateError;
+use crate::utils::types::{size::PhysicalSize, buffers::polygon_buffer::PolygonBuffer};
+
+/// How [`State::new`](crate::utils::state::State::new) picks an adapter and device.
+#[derive(Debug, Clone)]
+pub struct GpuConfig {
+    /// Backends to try, in order of preference. When one of them fails to produce a working
+    /// device the next one is tried. A GL attempt takes the canvas's `webgl2` context for good,
+    /// so a failed GL attempt ends the search with its error; keep it last.
+    pub backends: Vec<wgpu::Backends>,
+    pub power_preference: wgpu::PowerPreference,
+    pub required_features: wgpu::Features,
+    /// Limits to request from the device. `None` uses the defaults of whichever backend was
+    /// picked, raised to the largest resolution the adapter supports.
+    pub required_limits: Option<wgpu::Limits>,
+    pub force_fallback_adapter: bool,
+}
+
+impl Default for GpuConfig {
+    fn default() -> Self {
+        Self {
+            backends: vec![wgpu::Backends::BROWSER_WEBGPU, wgpu::Backends::GL],
+            power_preference: wgpu::PowerPreference::default(),
+            required_features: wgpu::Features::empty(),
+            required_limits: None,
+            force_fallback_adapter: false,
+        }
+    }
+}
+
+/// Everything that belongs to one `wgpu::Device`. When the device is lost none of it can be
+/// reused, so [`State`](crate::utils::state::State) throws it away and builds a new one.
+pub struct Gpu {
+    pub config: wgpu::SurfaceConfiguration,
+    pub is_surface_configured: bool,
+    pub polygon_buffer: PolygonBuffer<PolygonVertex>,
+    pub camera_buffer: wgpu::Buffer,
+    pub camera_bind_group: wgpu::BindGroup,
+
+    // portion of render structure
+    pub surface: wgpu::Surface<'static>,
+    pub render_pipeline: wgpu::RenderPipeline,
+    pub device: wgpu::Device,
+    pub queue: wgpu::Queue,
+    pub adapter_info: wgpu::AdapterInfo,
+    pub limits: wgpu::Limits,
+    // challenge variables
+    // challenge_render_pipeline: wgpu::RenderPipeline,
+
+    // set from the device lost callback, which wgpu may call from outside the render loop
+    lost: Arc<AtomicBool>,
+}
+
+impl Gpu {
+    /// Tries each of `config.backends` in turn and returns the first one that works,
+    /// or the error of the last one that was attempted.
+    pub async fn new(canvas: Rc<leptos::web_sys::HtmlCanvasElement>, scene: &Scene, config: &GpuConfig) -> Result<Gpu, StateError> {
+        // no point in probing webgpu when the browser doesn't expose it at all
+        let webgpu_supported = wgpu::util::is_browser_webgpu_supported().await;
+        let mut last_error = StateError::NoBackends;
+
+        for &backends in &config.backends {
+            let backends = if webgpu_supported {
+                backends
+            } else {
+                backends - wgpu::Backends::BROWSER_WEBGPU
+            };
+
+            if backends.is_empty() {
+                continue;
+            }
+
+            match Self::with_backends(canvas.clone(), scene, config, backends).await {
+                Ok(gpu) => return Ok(gpu),
+                Err(e) if backends.contains(wgpu::Backends::GL) => {
+                    // nothing else can get a context on the canvas anymore
+                    leptos::logging::warn!("Could not initialize {backends:?}, which leaves no backend to fall back to: {e}");
+                    return Err(e);
+                }
+                Err(e) => {
+                    leptos::logging::warn!("Could not initialize {backends:?}: {e}");
+                    last_error = e;
+                }
+            }
+        }
+
+        Err(last_error)
+    }
+
+    async fn with_backends(canvas: Rc<leptos::web_sys::HtmlCanvasElement>, scene: &Scene, config: &GpuConfig, backends: wgpu::Backends) -> Result<Gpu, StateError> {
+        // handle initialization
+        let canvas_size = PhysicalSize::<u32> {
+            width: canvas.width(),
+            height: canvas.height(),
+        };
+
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends,
+            ..Default::default()
+        });
+
+        // a canvas that once handed out a webgpu context can't hand out a webgl2 one anymore, so
+        // webgpu is brought up on a throwaway canvas and only moved to the real one once it works
+        let uses_webgpu = backends.contains(wgpu::Backends::BROWSER_WEBGPU);
+        let surface_canvas = if uses_webgpu {
+            Self::probe_canvas(&canvas)
+        } else {
+            canvas.as_ref().clone()
+        };
+        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(surface_canvas))?;
+
+        let adapter = instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: config.power_preference,
+            compatible_surface: Some(&surface),
+            force_fallback_adapter: config.force_fallback_adapter,
+        }).await?;
+
+        let adapter_info = adapter.get_info();
+        let required_limits = config.required_limits.clone().unwrap_or_else(|| {
+            let defaults = match adapter_info.backend {
+                wgpu::Backend::BrowserWebGpu => wgpu::Limits::default(),
+                _ => wgpu::Limits::downlevel_webgl2_defaults(),
+            };
+            defaults.using_resolution(adapter.limits())
+        });
+
+        let (device, queue) = adapter.request_device(
+        &wgpu::DeviceDescriptor {
+                required_features: config.required_features,
+                required_limits,
+                label: None,
+                memory_hints: Default::default(),
+                trace: wgpu::Trace::Off,
+            },
+        ).await?;
+
+        leptos::logging::log!("Using {:?} adapter \"{}\"", adapter_info.backend, adapter_info.name);
+
+        let lost = Arc::new(AtomicBool::new(false));
+        device.set_device_lost_callback({
+            let lost = lost.clone();
+            move |reason, message| {
+                leptos::logging::warn!("Device lost ({reason:?}): {message}");
+                lost.store(true, Ordering::Release);
+            }
+        });
+
+        let config = Self::generate_config(&adapter, &surface, &canvas_size);
+
+        // handle shaders
+        let shader = wgpu::include_wgsl!("./shaders/buffer_shader.wgsl");
+
+        // handle buffers
+        let polygon_buffer = PolygonBuffer::polygon_from_sides(&device, scene.polygon_sides, scene.polygon_radius, scene.vertex_color);
+
+        // the camera keeps the polygon aspect-correct without regenerating it on resize,
+        // and is rewritten every frame from `State::update`
+        let camera = Camera {
+            aspect: canvas_size.aspect(),
+            ..scene.camera
+        };
+        let camera_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Camera Buffer"),
+            contents: bytemuck::cast_slice(&[CameraUniform::from_camera(&camera)]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let camera_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Camera Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let camera_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Camera Bind Group"),
+            layout: &camera_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: camera_buffer.as_entire_binding(),
+            }],
+        });
+
+        // handle rendering
+        // shader compilation and pipeline validation errors are reported asynchronously, so catch them in a scope
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+
+        let render_pipeline = Self::generate_render_pipeline::<PolygonVertex>(
+    shader, 
+            &device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Render Pipeline Layout"),
+                bind_group_layouts: &[&camera_bind_group_layout],
+                push_constant_ranges: &[],
+            }),
+            &device, 
+            &config
+        );
+
+        if let Some(error) = device.pop_error_scope().await {
+            return Err(StateError::Shader(error.to_string()));
+        }
+
+        let surface = if uses_webgpu {
+            // webgpu's preferred canvas format is the same for every canvas, so `config` still fits
+            instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.as_ref().clone()))?
+        } else {
+            surface
+        };
+
+        let limits = device.limits();
+
+        Ok(Self {
+            surface,
+            device,
+            queue,
+            config,
+            is_surface_configured: false,
+            render_pipeline,
+            // challenge_render_pipeline,
+            polygon_buffer,
+            camera_buffer,
+            camera_bind_group,
+            limits,
+            adapter_info,
+            lost,
+        })
+    }
+
+    /// A detached canvas of the same size as `canvas`, to try webgpu on without claiming it.
+    fn probe_canvas(canvas: &leptos::web_sys::HtmlCanvasElement) -> leptos::web_sys::HtmlCanvasElement {
+        let probe: leptos::web_sys::HtmlCanvasElement = canvas
+            .owner_document()
+            .expect("canvas has no document")
+            .create_element("canvas")
+            .expect("Failed to create a canvas")
+            .unchecked_into();
+        probe.set_width(canvas.width());
+        probe.set_height(canvas.height());
+        probe
+    }
+
+    /// Whether the device behind these resources has been lost.
+    pub fn is_lost(&self) -> bool {
+        self.lost.load(Ordering::Acquire)
+    }
+
+    fn generate_config(adapter: &wgpu::Adapter, surface: &wgpu::Surface, canvas_size: &PhysicalSize<u32>) -> wgpu::SurfaceConfiguration {
+        let surface_caps = surface.get_capabilities(adapter);
+
+        let surface_format = surface_caps.formats.iter()
+            .find(|f| f.is_srgb())
+            .copied()
+            .unwrap_or(surface_caps.formats[0]);
+
+        wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width: canvas_size.width,
+            height: canvas_size.height,
+            present_mode: surface_caps.present_modes[0],
+            alpha_mode: surface_caps.alpha_modes[0],
+            view_formats: vec![],
+            desired_maximum_frame_latency: 2,
+        }
+    }
+
+    fn generate_render_pipeline<T: Vertex>(source: wgpu::ShaderModuleDescriptor, layout: &wgpu::PipelineLayout, device: &wgpu::Device, config: &wgpu::SurfaceConfiguration) -> wgpu::RenderPipeline {
+        let shader = device.create_shader_module(source);
+        
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Render Pipeline"),
+            layout: Some(layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"), // entry point in our wgsl code
+                buffers: &[
+                    T::desc(),
+                ], // any buffers we may require
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState { // defining our fragment
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState { // defining the targets for our fragment
+                    format: config.format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList, // defining our topology as a list of triangles
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw, // having our code read vertices CCW
+                cull_mode: Some(wgpu::Face::Back),
+                // Setting this to anything other than Fill requires Features::NON_FILL_POLYGON_MODE
+                polygon_mode: wgpu::PolygonMode::Fill,
+                // Requires Features::DEPTH_CLIP_CONTROL
+                unclipped_depth: false,
+                // Requires Features::CONSERVATIVE_RASTERIZATION
+                conservative: false,
+            },
+            // continued ...
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        })
+    }
+}
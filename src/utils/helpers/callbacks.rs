@@ -1,64 +1,442 @@
-use crate::utils::state::State;
-use std::{cell::RefCell, rc::Rc, sync::Arc};
-use wasm_bindgen_futures::wasm_bindgen::{prelude::Closure, JsCast};
-
-// pub fn _mouse_moved_callback(window: &Rc<wgpu::web_sys::Window>, state: Rc<RefCell<State<'static>>>) {
-//     let mouse_moved_closure: Closure<dyn FnMut(leptos::web_sys::MouseEvent)> =
-//         Closure::wrap(Box::new({
-//             move |event: leptos::web_sys::MouseEvent| {
-//                 let mouse_pos = PhysicalSize {
-//                     width: event.client_x() as u32,
-//                     height: event.client_y() as u32,
-//                 };
-
-//                 state.borrow_mut().mouse_challenge(mouse_pos);
-//             }
-//         }) as Box<dyn FnMut(leptos::web_sys::MouseEvent)>);
-
-//     window
-//         .add_event_listener_with_callback("mousemove", mouse_moved_closure.as_ref().unchecked_ref())
-//         .unwrap();
-
-//     mouse_moved_closure.forget()
-// }
-
-pub fn keydown_callback(window: &Rc<wgpu::web_sys::Window>, state: Rc<RefCell<State<'static>>>) {
+use crate::utils::helpers::canvas::{scale_factor, set_canvas_size, supports_device_pixel_content_box};
+use crate::utils::renderer::Renderer;
+use crate::utils::types::action_map::{ActionEvent, ActiveActions};
+use crate::utils::types::gamepad::GamepadConnection;
+use crate::utils::types::key_event::KeyEvent;
+use crate::utils::types::render_mode::RedrawHandle;
+use crate::utils::types::pointer_event::{PointerEvent, WheelEvent};
+use crate::utils::types::size::{LogicalSize, PhysicalSize};
+use std::{any::Any, cell::{Cell, RefCell}, rc::Rc};
+use leptos::prelude::{GetUntracked, Set, Signal, WriteSignal};
+use leptos::web_sys::{
+    js_sys::{Array, Function},
+    EventTarget, IntersectionObserver, IntersectionObserverEntry, ResizeObserver, ResizeObserverBoxOptions, ResizeObserverEntry, ResizeObserverOptions, ResizeObserverSize,
+};
+use wasm_bindgen_futures::wasm_bindgen::{closure::WasmClosure, prelude::Closure, JsCast};
+
+/// A listener registered on an [`EventTarget`]; it is removed again when this handle is dropped.
+pub struct EventListener {
+    target: EventTarget,
+    event: &'static str,
+    function: Function,
+    // keeps the rust side of the callback alive for as long as the listener is registered
+    _closure: Box<dyn Any>,
+}
+
+impl EventListener {
+    pub fn new<T: ?Sized + WasmClosure + 'static>(target: &EventTarget, event: &'static str, closure: Closure<T>) -> Self {
+        let function: Function = closure.as_ref().unchecked_ref::<Function>().clone();
+
+        target
+            .add_event_listener_with_callback(event, &function)
+            .unwrap();
+
+        Self {
+            target: target.clone(),
+            event,
+            function,
+            _closure: Box::new(closure),
+        }
+    }
+}
+
+impl Drop for EventListener {
+    fn drop(&mut self) {
+        let _ = self.target.remove_event_listener_with_callback(self.event, &self.function);
+    }
+}
+
+/// An observer watching the canvas; it is disconnected when this handle is dropped.
+pub struct Observer {
+    disconnect: Box<dyn Fn()>,
+    // keeps the rust side of the callback alive for as long as the observer is connected
+    _closure: Box<dyn Any>,
+}
+
+impl Observer {
+    pub fn new<T: ?Sized + WasmClosure + 'static>(closure: Closure<T>, disconnect: impl Fn() + 'static) -> Self {
+        Self {
+            disconnect: Box::new(disconnect),
+            _closure: Box::new(closure),
+        }
+    }
+}
+
+impl Drop for Observer {
+    fn drop(&mut self) {
+        (self.disconnect)();
+    }
+}
+
+pub fn pointerdown_callback<R: Renderer>(
+    canvas: &Rc<wgpu::web_sys::HtmlCanvasElement>,
+    renderer: Rc<RefCell<R>>,
+) -> EventListener {
+    let pointerdown_closure: Closure<dyn FnMut(leptos::web_sys::PointerEvent)> =
+        Closure::wrap(Box::new({
+            let canvas = canvas.clone();
+            move |event: leptos::web_sys::PointerEvent| {
+                // keeps pointermove and pointerup coming while a drag leaves the canvas
+                let _ = canvas.set_pointer_capture(event.pointer_id());
+                renderer.borrow_mut().handle_pointer_down(PointerEvent::from_event(&event, &canvas));
+            }
+        }) as Box<dyn FnMut(leptos::web_sys::PointerEvent)>);
+
+    EventListener::new(canvas, "pointerdown", pointerdown_closure)
+}
+
+pub fn pointerup_callback<R: Renderer>(
+    canvas: &Rc<wgpu::web_sys::HtmlCanvasElement>,
+    renderer: Rc<RefCell<R>>,
+) -> EventListener {
+    let pointerup_closure: Closure<dyn FnMut(leptos::web_sys::PointerEvent)> =
+        Closure::wrap(Box::new({
+            let canvas = canvas.clone();
+            move |event: leptos::web_sys::PointerEvent| {
+                renderer.borrow_mut().handle_pointer_up(PointerEvent::from_event(&event, &canvas));
+            }
+        }) as Box<dyn FnMut(leptos::web_sys::PointerEvent)>);
+
+    EventListener::new(canvas, "pointerup", pointerup_closure)
+}
+
+/// Listens for the browser taking a pointer over, which must not count as it being let go.
+pub fn pointercancel_callback<R: Renderer>(
+    canvas: &Rc<wgpu::web_sys::HtmlCanvasElement>,
+    renderer: Rc<RefCell<R>>,
+) -> EventListener {
+    let pointercancel_closure: Closure<dyn FnMut(leptos::web_sys::PointerEvent)> =
+        Closure::wrap(Box::new({
+            let canvas = canvas.clone();
+            move |event: leptos::web_sys::PointerEvent| {
+                renderer.borrow_mut().handle_pointer_cancel(PointerEvent::from_event(&event, &canvas));
+            }
+        }) as Box<dyn FnMut(leptos::web_sys::PointerEvent)>);
+
+    EventListener::new(canvas, "pointercancel", pointercancel_closure)
+}
+
+pub fn pointermove_callback<R: Renderer>(
+    canvas: &Rc<wgpu::web_sys::HtmlCanvasElement>,
+    renderer: Rc<RefCell<R>>,
+) -> EventListener {
+    let pointermove_closure: Closure<dyn FnMut(leptos::web_sys::PointerEvent)> =
+        Closure::wrap(Box::new({
+            let canvas = canvas.clone();
+            move |event: leptos::web_sys::PointerEvent| {
+                renderer.borrow_mut().handle_pointer_move(PointerEvent::from_event(&event, &canvas));
+            }
+        }) as Box<dyn FnMut(leptos::web_sys::PointerEvent)>);
+
+    EventListener::new(canvas, "pointermove", pointermove_closure)
+}
+
+pub fn wheel_callback<R: Renderer>(canvas: &Rc<wgpu::web_sys::HtmlCanvasElement>, renderer: Rc<RefCell<R>>) -> EventListener {
+    let wheel_closure: Closure<dyn FnMut(leptos::web_sys::WheelEvent)> = Closure::wrap(Box::new({
+        let canvas = canvas.clone();
+        move |event: leptos::web_sys::WheelEvent| {
+            // the wheel belongs to the scene, not to scrolling the page
+            event.prevent_default();
+            renderer.borrow_mut().handle_wheel(WheelEvent::from_event(&event, &canvas));
+        }
+    }) as Box<dyn FnMut(leptos::web_sys::WheelEvent)>);
+
+    EventListener::new(canvas, "wheel", wheel_closure)
+}
+
+/// Whether `event` was aimed at a text field or other editable element, whose keys belong
+/// to the user typing rather than to the scene.
+fn targets_editable(event: &leptos::web_sys::Event) -> bool {
+    let Some(element) = event.target().and_then(|target| target.dyn_into::<leptos::web_sys::HtmlElement>().ok()) else {
+        return false;
+    };
+
+    element.is_content_editable() || matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+}
+
+/// Listens for key presses on `target`: the canvas itself, so only a focused canvas sees
+/// them, or the window for global capture. Triggered actions are recorded in `active` for
+/// [`keyup_callback`] to release.
+pub fn keydown_callback<R: Renderer>(target: &EventTarget, renderer: Rc<RefCell<R>>, active: Rc<RefCell<ActiveActions>>) -> EventListener {
     let keydown_closure: Closure<dyn FnMut(leptos::web_sys::KeyboardEvent)> =
         Closure::wrap(Box::new({
             move |event: leptos::web_sys::KeyboardEvent| {
-                state.borrow_mut().handle_key(event).unwrap();
+                if targets_editable(&event) {
+                    return;
+                }
+
+                let event = KeyEvent::from_event(&event);
+                let mut renderer = renderer.borrow_mut();
+                renderer.handle_key(event);
+
+                let action = renderer
+                    .action_map()
+                    .and_then(|actions| actions.resolve(&event))
+                    .map(str::to_owned);
+                if let Some(action) = action {
+                    active.borrow_mut().press(event.physical, &action);
+                    renderer.handle_action(ActionEvent {
+                        action,
+                        pressed: true,
+                        repeat: event.repeat,
+                    });
+                }
             }
         }) as Box<dyn FnMut(leptos::web_sys::KeyboardEvent)>);
 
-    window
-        .add_event_listener_with_callback("keydown", keydown_closure.as_ref().unchecked_ref())
-        .unwrap();
+    EventListener::new(target, "keydown", keydown_closure)
+}
+
+/// Listens for key releases on `target`. Unlike presses these are never filtered, so a key
+/// held while focus moves into a text field is still let go. Only the actions its `keydown`
+/// triggered are released.
+pub fn keyup_callback<R: Renderer>(target: &EventTarget, renderer: Rc<RefCell<R>>, active: Rc<RefCell<ActiveActions>>) -> EventListener {
+    let keyup_closure: Closure<dyn FnMut(leptos::web_sys::KeyboardEvent)> =
+        Closure::wrap(Box::new({
+            move |event: leptos::web_sys::KeyboardEvent| {
+                let event = KeyEvent::from_event(&event);
+                let mut renderer = renderer.borrow_mut();
+                renderer.handle_key_up(event);
+
+                let actions = active.borrow_mut().release(event.physical);
+                for action in actions {
+                    renderer.handle_action(ActionEvent {
+                        action,
+                        pressed: false,
+                        repeat: false,
+                    });
+                }
+            }
+        }) as Box<dyn FnMut(leptos::web_sys::KeyboardEvent)>);
 
-    keydown_closure.forget()
+    EventListener::new(target, "keyup", keyup_closure)
 }
 
-pub fn resize_callback(
-    window: &Rc<wgpu::web_sys::Window>,
-    state: Rc<RefCell<State<'static>>>,
-    canvas: Arc<wgpu::web_sys::HtmlCanvasElement>,
-) {
-    let resize_closure: Closure<dyn FnMut()> = Closure::wrap(Box::new({
+/// Listens for `target` losing focus, after which its held keys see no `keyup`, so their
+/// actions are released right away.
+pub fn blur_callback<R: Renderer>(target: &EventTarget, renderer: Rc<RefCell<R>>, active: Rc<RefCell<ActiveActions>>) -> EventListener {
+    let blur_closure: Closure<dyn FnMut()> = Closure::wrap(Box::new({
         move || {
+            let mut renderer = renderer.borrow_mut();
+            renderer.handle_blur();
+
+            let actions = active.borrow_mut().release_all();
+            for action in actions {
+                renderer.handle_action(ActionEvent {
+                    action,
+                    pressed: false,
+                    repeat: false,
+                });
+            }
+        }
+    }) as Box<dyn FnMut()>);
+
+    EventListener::new(target, "blur", blur_closure)
+}
+
+/// Keeps the canvas backing store in sync with the canvas's own box, so it follows layout
+/// changes (flex/grid parents, collapsing sidebars, resizable panels) and not just the window.
+pub fn resize_observer_callback<R: Renderer>(
+    renderer: Rc<RefCell<R>>,
+    canvas: Rc<wgpu::web_sys::HtmlCanvasElement>,
+    redraw: RedrawHandle,
+) -> Observer {
+    let device_pixel_box = supports_device_pixel_content_box();
+
+    let resize_closure: Closure<dyn FnMut(Array)> = Closure::wrap(Box::new({
+        let canvas = canvas.clone();
+        move |entries: Array| {
             // leptos::logging::log!("Resizing");
 
-            let width = canvas.client_width() as u32;
-            let height = canvas.client_height() as u32;
+            // only the canvas is observed, so the last entry holds its latest size
+            let Some(entry) = entries.iter().last() else {
+                return;
+            };
+            let entry: ResizeObserverEntry = entry.unchecked_into();
+
+            let scale_factor = scale_factor();
+            let size = if device_pixel_box {
+                let size: ResizeObserverSize = entry.device_pixel_content_box_size().get(0).unchecked_into();
+                PhysicalSize {
+                    width: size.inline_size() as u32,
+                    height: size.block_size() as u32,
+                }
+            } else {
+                let size: ResizeObserverSize = entry.content_box_size().get(0).unchecked_into();
+                LogicalSize {
+                    width: size.inline_size(),
+                    height: size.block_size(),
+                }
+                .to_physical(scale_factor)
+            };
+
+            let max_dimension = renderer.borrow().max_texture_dimension();
+            set_canvas_size(&canvas, size, max_dimension);
+
+            renderer.borrow_mut().resize(canvas.clone(), scale_factor);
+            // resizing clears the canvas, so it needs a frame even when nothing else changed
+            redraw.request_redraw();
+        }
+    }) as Box<dyn FnMut(Array)>);
+
+    let observer = ResizeObserver::new(resize_closure.as_ref().unchecked_ref()).unwrap();
+
+    if device_pixel_box {
+        let options = ResizeObserverOptions::new();
+        options.set_box(ResizeObserverBoxOptions::DevicePixelContentBox);
+        observer.observe_with_options(&canvas, &options);
+    } else {
+        observer.observe(&canvas);
+    }
+
+    Observer::new(resize_closure, move || observer.disconnect())
+}
+
+/// Asks for the pointer lock whenever the canvas is clicked while `enabled`; browsers only
+/// grant it in response to a user gesture.
+pub fn request_pointer_lock_callback(canvas: &Rc<wgpu::web_sys::HtmlCanvasElement>, enabled: Signal<bool>) -> EventListener {
+    let click_closure: Closure<dyn FnMut()> = Closure::wrap(Box::new({
+        let canvas = canvas.clone();
+        move || {
+            if enabled.get_untracked() {
+                canvas.request_pointer_lock();
+            }
+        }
+    }) as Box<dyn FnMut()>);
+
+    EventListener::new(canvas, "click", click_closure)
+}
+
+/// Tells the renderer and `locked` whenever the canvas gains or loses the pointer lock.
+pub fn pointerlockchange_callback<R: Renderer>(
+    canvas: &Rc<wgpu::web_sys::HtmlCanvasElement>,
+    renderer: Rc<RefCell<R>>,
+    locked: Option<WriteSignal<bool>>,
+) -> EventListener {
+    let document = canvas.owner_document().expect("canvas is not in a document");
+
+    let change_closure: Closure<dyn FnMut()> = Closure::wrap(Box::new({
+        let canvas = canvas.clone();
+        let document = document.clone();
+        move || {
+            let is_locked = document
+                .pointer_lock_element()
+                .is_some_and(|element| element == ***canvas);
+
+            renderer.borrow_mut().handle_pointer_lock(is_locked);
+            if let Some(locked) = locked {
+                locked.try_set(is_locked);
+            }
+        }
+    }) as Box<dyn FnMut()>);
+
+    EventListener::new(&document, "pointerlockchange", change_closure)
+}
+
+/// Releases the pointer lock on Escape, for browsers that pass the key on to the page first.
+/// A lock held by any other element on the page is left alone.
+pub fn exit_pointer_lock_callback(
+    window: &Rc<wgpu::web_sys::Window>,
+    canvas: &Rc<wgpu::web_sys::HtmlCanvasElement>,
+) -> EventListener {
+    let document = canvas.owner_document().expect("canvas is not in a document");
+
+    let keydown_closure: Closure<dyn FnMut(leptos::web_sys::KeyboardEvent)> =
+        Closure::wrap(Box::new({
+            let canvas = canvas.clone();
+            move |event: leptos::web_sys::KeyboardEvent| {
+                if event.code() != "Escape" {
+                    return;
+                }
+
+                if document
+                    .pointer_lock_element()
+                    .is_some_and(|element| element == ***canvas)
+                {
+                    document.exit_pointer_lock();
+                }
+            }
+        }) as Box<dyn FnMut(leptos::web_sys::KeyboardEvent)>);
 
-            canvas.set_width(width);
-            canvas.set_height(height);
+    EventListener::new(window, "keydown", keydown_closure)
+}
+
+/// Listens for `event` (`gamepadconnected` or `gamepaddisconnected`) on the window.
+pub fn gamepad_connection_callback<R: Renderer>(
+    window: &Rc<wgpu::web_sys::Window>,
+    renderer: Rc<RefCell<R>>,
+    event: &'static str,
+) -> EventListener {
+    let connected = event == "gamepadconnected";
+
+    let gamepad_closure: Closure<dyn FnMut(leptos::web_sys::GamepadEvent)> =
+        Closure::wrap(Box::new({
+            move |event: leptos::web_sys::GamepadEvent| {
+                let Some(gamepad) = event.gamepad() else {
+                    return;
+                };
+
+                renderer.borrow_mut().handle_gamepad_connection(GamepadConnection {
+                    index: gamepad.index(),
+                    id: gamepad.id(),
+                    connected,
+                });
+            }
+        }) as Box<dyn FnMut(leptos::web_sys::GamepadEvent)>);
 
-            state.borrow_mut().resize(canvas.clone());
+    EventListener::new(window, event, gamepad_closure)
+}
+
+/// Keeps `page_visible` in sync with `document.hidden` and calls `on_change` whenever it flips.
+pub fn visibilitychange_callback(
+    document: &leptos::web_sys::Document,
+    page_visible: Rc<Cell<bool>>,
+    on_change: Rc<dyn Fn()>,
+) -> EventListener {
+    let visibility_closure: Closure<dyn FnMut()> = Closure::wrap(Box::new({
+        let document = document.clone();
+        move || {
+            page_visible.set(!document.hidden());
+            on_change();
         }
     }) as Box<dyn FnMut()>);
 
-    window
-        .add_event_listener_with_callback("resize", resize_closure.as_ref().unchecked_ref())
-        .unwrap();
+    EventListener::new(document, "visibilitychange", visibility_closure)
+}
+
+/// Keeps `onscreen` in sync with whether any part of the canvas is inside the viewport and
+/// calls `on_change` whenever it flips.
+pub fn intersection_observer_callback(
+    canvas: &Rc<wgpu::web_sys::HtmlCanvasElement>,
+    onscreen: Rc<Cell<bool>>,
+    on_change: Rc<dyn Fn()>,
+) -> Observer {
+    let intersection_closure: Closure<dyn FnMut(Array)> = Closure::wrap(Box::new({
+        move |entries: Array| {
+            // only the canvas is observed, so the last entry holds its latest state
+            let Some(entry) = entries.iter().last() else {
+                return;
+            };
+            let entry: IntersectionObserverEntry = entry.unchecked_into();
+
+            onscreen.set(entry.is_intersecting());
+            on_change();
+        }
+    }) as Box<dyn FnMut(Array)>);
+
+    let observer = IntersectionObserver::new(intersection_closure.as_ref().unchecked_ref()).unwrap();
+    observer.observe(canvas);
+
+    Observer::new(intersection_closure, move || observer.disconnect())
+}
+
+/// Asks for a frame whenever `event` fires on `target`, for [`RenderMode::OnDemand`](crate::RenderMode::OnDemand).
+pub fn redraw_callback(target: &EventTarget, event: &'static str, redraw: RedrawHandle) -> EventListener {
+    let redraw_closure: Closure<dyn FnMut()> = Closure::wrap(Box::new({
+        move || {
+            redraw.request_redraw();
+        }
+    }) as Box<dyn FnMut()>);
 
-    resize_closure.forget()
+    EventListener::new(target, event, redraw_closure)
 }
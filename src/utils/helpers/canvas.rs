@@ -0,0 +1,40 @@
+use leptos::web_sys::js_sys;
+
+use crate::utils::types::size::{LogicalSize, PhysicalSize};
+
+/// The `devicePixelRatio` of the page, or `1.0` outside of a browser window.
+pub fn scale_factor() -> f64 {
+    leptos::web_sys::window()
+        .map(|window| window.device_pixel_ratio())
+        .unwrap_or(1.0)
+}
+
+/// Sets the canvas backing store to its CSS size times `scale_factor`, scaled down to fit
+/// `max_dimension` so it never exceeds what the device can render to.
+pub fn fit_canvas(canvas: &leptos::web_sys::HtmlCanvasElement, scale_factor: f64, max_dimension: u32) -> PhysicalSize<u32> {
+    let logical = LogicalSize {
+        width: canvas.client_width() as f64,
+        height: canvas.client_height() as f64,
+    };
+
+    set_canvas_size(canvas, logical.to_physical(scale_factor), max_dimension)
+}
+
+/// Sets the canvas backing store to `size`, scaled down to fit `max_dimension`.
+pub fn set_canvas_size(canvas: &leptos::web_sys::HtmlCanvasElement, size: PhysicalSize<u32>, max_dimension: u32) -> PhysicalSize<u32> {
+    let physical = size.clamp(max_dimension);
+
+    canvas.set_width(physical.width);
+    canvas.set_height(physical.height);
+
+    physical
+}
+
+/// Whether `ResizeObserver` can report `devicePixelContentBoxSize`, which gives the exact
+/// backing size in device pixels instead of a rounded CSS size times the scale factor.
+pub fn supports_device_pixel_content_box() -> bool {
+    js_sys::Reflect::get(&js_sys::global(), &"ResizeObserverEntry".into())
+        .and_then(|entry| js_sys::Reflect::get(&entry, &"prototype".into()))
+        .and_then(|prototype| js_sys::Reflect::has(&prototype, &"devicePixelContentBoxSize".into()))
+        .unwrap_or(false)
+}
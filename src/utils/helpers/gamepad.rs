@@ -0,0 +1,39 @@
+use leptos::wasm_bindgen::JsCast;
+
+use crate::utils::types::gamepad::{GamepadButtonState, GamepadSnapshot};
+
+/// Reads every connected gamepad from `navigator.getGamepads()`. Browsers only update gamepad
+/// state when asked, so this is called once per frame.
+pub fn poll_gamepads(navigator: &leptos::web_sys::Navigator) -> Vec<GamepadSnapshot> {
+    let Ok(gamepads) = navigator.get_gamepads() else {
+        return Vec::new();
+    };
+
+    gamepads
+        .iter()
+        // disconnected slots are `null`
+        .filter_map(|gamepad| gamepad.dyn_into::<leptos::web_sys::Gamepad>().ok())
+        .filter(|gamepad| gamepad.connected())
+        .map(|gamepad| GamepadSnapshot {
+            index: gamepad.index(),
+            id: gamepad.id(),
+            standard: gamepad.mapping() == leptos::web_sys::GamepadMappingType::Standard,
+            buttons: gamepad
+                .buttons()
+                .iter()
+                .map(|button| {
+                    let button: leptos::web_sys::GamepadButton = button.unchecked_into();
+                    GamepadButtonState {
+                        pressed: button.pressed(),
+                        value: button.value() as f32,
+                    }
+                })
+                .collect(),
+            axes: gamepad
+                .axes()
+                .iter()
+                .map(|axis| axis.as_f64().unwrap_or(0.0) as f32)
+                .collect(),
+        })
+        .collect()
+}
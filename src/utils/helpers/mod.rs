@@ -1 +1,5 @@
-pub mod callbacks;
\ No newline at end of file
+pub mod callbacks;
+pub mod canvas;
+pub mod gamepad;
+#[cfg(test)]
+pub mod testing;
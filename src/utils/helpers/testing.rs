@@ -0,0 +1,38 @@
+use wasm_bindgen_futures::{js_sys, JsFuture};
+
+use crate::utils::types::key_event::Modifiers;
+use crate::utils::types::pointer_event::{PointerButton, PointerEvent, PointerKind, PointerPosition};
+use crate::utils::types::size::{PhysicalPosition, PhysicalSize};
+
+/// Waits `ms` milliseconds on the browser's event loop, letting spawned tasks and device
+/// callbacks run in the meantime.
+pub async fn sleep(ms: i32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        leptos::web_sys::window()
+            .expect("no global window")
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .expect("Failed to set a timeout");
+    });
+    JsFuture::from(promise).await.expect("Timeout promise was rejected");
+}
+
+/// A touch contact at `x, y` on a 200 by 200 pixel canvas, `time_stamp` milliseconds in.
+pub fn touch(pointer_id: i32, x: f64, y: f64, time_stamp: f64) -> PointerEvent {
+    let physical = PhysicalPosition { x, y };
+
+    PointerEvent {
+        pointer_id,
+        kind: PointerKind::Touch,
+        position: PointerPosition {
+            physical,
+            ndc: physical.to_ndc(PhysicalSize {
+                width: 200,
+                height: 200,
+            }),
+        },
+        button: Some(PointerButton::Primary),
+        movement: [0.0, 0.0],
+        modifiers: Modifiers::empty(),
+        time_stamp,
+    }
+}
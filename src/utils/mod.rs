@@ -1,3 +1,6 @@
+pub mod controllers;
+pub mod gpu;
 pub mod helpers;
+pub mod renderer;
 pub mod state;
 pub mod types;
@@ -0,0 +1,140 @@
+use std::future::Future;
+use std::rc::Rc;
+
+use leptos::web_sys::HtmlCanvasElement;
+
+use crate::utils::helpers::canvas::scale_factor;
+use crate::utils::types::action_map::{ActionEvent, ActionMap};
+use crate::utils::types::frame_time::FrameTime;
+use crate::utils::types::gamepad::{GamepadConnection, GamepadSnapshot};
+use crate::utils::types::key_event::KeyEvent;
+use crate::utils::types::render_mode::RedrawHandle;
+use crate::utils::types::pointer_event::{PointerEvent, WheelEvent};
+
+/// A scene that can be mounted inside a [`WgpuCanvas`](crate::WgpuCanvas).
+///
+/// The component owns the canvas sizing, the `request_animation_frame` loop and the
+/// event listeners; implementors only have to deal with their own GPU state.
+pub trait Renderer: Sized + 'static {
+    /// Why [`Renderer::init`] failed, e.g. [`StateError`](crate::StateError).
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// How the renderer is set up, e.g. [`GpuConfig`](crate::GpuConfig). Passed to
+    /// [`Renderer::init`] from the `config` prop of the [`WgpuCanvas`](crate::WgpuCanvas),
+    /// or its default when the prop is left out.
+    type Config: Default + 'static;
+
+    /// Scene parameters the page changes while the renderer runs, e.g.
+    /// [`SceneUpdate`](crate::SceneUpdate). Set through the `params` prop of the
+    /// [`WgpuCanvas`](crate::WgpuCanvas) and handed to [`Renderer::set_params`].
+    type Params: Clone + Send + Sync + 'static;
+
+    /// Builds the renderer once the canvas has been mounted and given its initial size.
+    /// An error here is shown by the nearest `<ErrorBoundary/>` instead of panicking.
+    fn init(canvas: Rc<HtmlCanvasElement>, config: Self::Config) -> impl Future<Output = Result<Self, Self::Error>>;
+
+    /// Advances the scene by one frame, called right before [`Renderer::render`].
+    fn update(&mut self, time: FrameTime);
+
+    /// Advances a simulation by exactly `time.delta` seconds. Called zero or more times per
+    /// frame, before [`Renderer::update`], when the canvas has a `fixed_timestep`. Ignored by default.
+    fn fixed_update(&mut self, _time: FrameTime) {}
+
+    /// Draws the current frame to the canvas surface.
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError>;
+
+    /// An error that leaves the renderer with nothing to draw with anymore, e.g. a lost device
+    /// that could not be recreated. Checked after every [`Renderer::update`]; returning one
+    /// stops the loop and throws it to the nearest `<ErrorBoundary/>`. `None` by default.
+    fn take_error(&mut self) -> Option<Self::Error> {
+        None
+    }
+
+    /// Reconfigures the surface after the canvas backing size has changed. The canvas is
+    /// already sized in physical pixels; `scale_factor` is the `devicePixelRatio` it was
+    /// scaled by, for anything that has to stay a constant size in CSS pixels.
+    fn resize(&mut self, canvas: Rc<HtmlCanvasElement>, scale_factor: f64);
+
+    /// Reconfigures the surface after [`Renderer::render`] reported it lost or outdated.
+    /// Defaults to [`Renderer::resize`] with the current canvas.
+    fn reconfigure(&mut self, canvas: Rc<HtmlCanvasElement>) {
+        self.resize(canvas, scale_factor());
+    }
+
+    /// The largest width or height the canvas backing store may be given, usually the
+    /// device's `max_texture_dimension_2d`. Defaults to the WebGL2 minimum.
+    fn max_texture_dimension(&self) -> u32 {
+        wgpu::Limits::downlevel_webgl2_defaults().max_texture_dimension_2d
+    }
+
+    /// Takes over the latest value of the [`WgpuCanvas`](crate::WgpuCanvas) `params` signal,
+    /// right before the next [`Renderer::update`] after it changed. Ignored by default.
+    fn set_params(&mut self, _params: Self::Params) {}
+
+    /// Receives the handle for asking the canvas to draw another frame, once, right after
+    /// [`Renderer::init`]. Only needed in [`RenderMode::OnDemand`](crate::RenderMode::OnDemand)
+    /// for changes that don't come from input or signals. Ignored by default.
+    fn set_redraw_handle(&mut self, _handle: RedrawHandle) {}
+
+    /// Whether another frame should follow this one in
+    /// [`RenderMode::OnDemand`](crate::RenderMode::OnDemand), e.g. while keys are held or an
+    /// animation is still running. `false` by default.
+    fn wants_redraw(&self) -> bool {
+        false
+    }
+
+    /// The adapter the renderer runs on, shown in [`RenderStats`](crate::RenderStats).
+    /// `None` by default.
+    fn adapter_info(&self) -> Option<&wgpu::AdapterInfo> {
+        None
+    }
+
+    /// Reacts to a key being pressed while the page has focus. Ignored by default.
+    fn handle_key(&mut self, _event: KeyEvent) {}
+
+    /// Reacts to a key being released while the page has focus. Ignored by default.
+    fn handle_key_up(&mut self, _event: KeyEvent) {}
+
+    /// Reacts to a button or contact going down on the canvas. Ignored by default.
+    fn handle_pointer_down(&mut self, _event: PointerEvent) {}
+
+    /// Reacts to a button or contact being released. Ignored by default.
+    fn handle_pointer_up(&mut self, _event: PointerEvent) {}
+
+    /// Reacts to the browser taking a contact over, e.g. for a system gesture, after which
+    /// no `pointerup` arrives for it. Defaults to [`Renderer::handle_pointer_up`].
+    fn handle_pointer_cancel(&mut self, event: PointerEvent) {
+        self.handle_pointer_up(event)
+    }
+
+    /// Reacts to a pointer moving over the canvas, or anywhere while it holds a button
+    /// pressed on it. Ignored by default.
+    fn handle_pointer_move(&mut self, _event: PointerEvent) {}
+
+    /// Reacts to the wheel being scrolled over the canvas. Ignored by default.
+    fn handle_wheel(&mut self, _event: WheelEvent) {}
+
+    /// Reacts to the canvas gaining or losing the pointer lock. Ignored by default.
+    fn handle_pointer_lock(&mut self, _locked: bool) {}
+
+    /// Receives the state of every connected gamepad, polled right before each
+    /// [`Renderer::update`]. Ignored by default.
+    fn handle_gamepads(&mut self, _gamepads: &[GamepadSnapshot]) {}
+
+    /// Reacts to a gamepad being connected or disconnected. Ignored by default.
+    fn handle_gamepad_connection(&mut self, _event: GamepadConnection) {}
+
+    /// The bindings keyboard events are resolved against before being handed to
+    /// [`Renderer::handle_action`]. `None` by default, i.e. no actions are triggered.
+    fn action_map(&self) -> Option<&ActionMap> {
+        None
+    }
+
+    /// Reacts to a chord from [`Renderer::action_map`] being pressed or released, after the
+    /// raw [`Renderer::handle_key`]/[`Renderer::handle_key_up`]. Ignored by default.
+    fn handle_action(&mut self, _event: ActionEvent) {}
+
+    /// Reacts to the page losing focus, after which no `keyup` arrives for keys that are
+    /// still held. Ignored by default.
+    fn handle_blur(&mut self) {}
+}
@@ -1,3 +1,11 @@
+struct Camera {
+    view_proj: mat4x4<f32>,
+};
+
+// maps world units to clip space, updated every frame from the camera
+@group(0) @binding(0)
+var<uniform> camera: Camera;
+
 struct VertexInput {
     @location(0) position: vec3<f32>,
     @location(1) color: vec3<f32>,
@@ -14,7 +22,7 @@ fn vs_main(
 ) -> VertexOutput {
     var out: VertexOutput;
     out.color = model.color;
-    out.clip_position = vec4<f32>(model.position, 1.0);
+    out.clip_position = camera.view_proj * vec4<f32>(model.position, 1.0);
     return out;
 }
 
@@ -1,223 +1,412 @@
-use std::sync::Arc;
-use std::str::FromStr;
-use wgpu::util::DeviceExt;
-
-use crate::utils::types::buffers::{Vertex, polygon_vertex::PolygonVertex};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use wasm_bindgen_futures::spawn_local;
+
+use crate::utils::controllers::{CameraController, pan_zoom::PanZoomController};
+use crate::utils::gpu::{Gpu, GpuConfig};
+use crate::utils::renderer::Renderer;
+use crate::utils::types::action_map::{ActionEvent, ActionMap};
+use crate::utils::types::buffers::{camera_uniform::CameraUniform, polygon_buffer::PolygonBuffer};
+use crate::utils::types::camera::{Camera, CameraMode};
+use crate::utils::types::input_state::InputState;
+use crate::utils::types::frame_time::FrameTime;
+use crate::utils::types::gamepad::{GamepadConnection, GamepadSnapshot};
+use crate::utils::types::gesture::Gesture;
+use crate::utils::types::key_event::KeyEvent;
 use crate::utils::types::keycode::KeyCode;
-use crate::utils::types::{size::PhysicalSize, buffers::polygon_buffer::PolygonBuffer};
-
-pub struct State<'a> {
-    config: wgpu::SurfaceConfiguration,
-    is_surface_configured: bool,
-    canvas: Arc<leptos::web_sys::HtmlCanvasElement>,
-    polygon_buffer: PolygonBuffer<PolygonVertex>,
-
-    // portion of render structure
-    surface: wgpu::Surface<'a>,
-    render_pipeline: wgpu::RenderPipeline,
-    device: wgpu::Device,
-    queue: wgpu::Queue,
-    // challenge variables
-    // challenge_render_pipeline: wgpu::RenderPipeline,
-    clear_color: wgpu::Color,
-    // toggle: bool,
+use crate::utils::types::pointer_event::{PointerEvent, WheelEvent};
+use crate::utils::types::scene_update::SceneUpdate;
+use crate::utils::types::size::PhysicalSize;
+use crate::utils::types::state_error::StateError;
+
+/// App-level scene data. Unlike [`Gpu`] it survives the device being lost.
+#[derive(Debug, Clone)]
+pub struct Scene {
+    pub clear_color: wgpu::Color,
+    pub polygon_sides: u16,
+    pub polygon_radius: f32,
+    pub vertex_color: [f32; 3],
+    pub camera: Camera,
 }
 
-impl<'a> State<'a> {
-    pub async fn new(canvas: Arc<leptos::web_sys::HtmlCanvasElement>) -> anyhow::Result<State<'a>> {
-        // handle initialization
-        let canvas_size = PhysicalSize::<u32> {
-            width: canvas.width(),
-            height: canvas.height(),
-        };
-
-        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
-            backends: wgpu::Backends::all(),
-            ..Default::default()
-        });
-
-        let surface = instance.create_surface(wgpu::SurfaceTarget::Canvas(canvas.as_ref().clone()))?;
-
-        let adapter = instance.request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::default(),
-            compatible_surface: Some(&surface),
-            force_fallback_adapter: false,
-        }).await?;
-
-        let (device, queue) = adapter.request_device(
-        &wgpu::DeviceDescriptor {
-                required_features: wgpu::Features::empty(),
-                required_limits: wgpu::Limits::downlevel_webgl2_defaults(),
-                label: None,
-                memory_hints: Default::default(),
-                trace: wgpu::Trace::Off,
+impl Default for Scene {
+    fn default() -> Self {
+        Self {
+            clear_color: wgpu::Color {
+                r: 0.1,
+                g: 0.2,
+                b: 0.3,
+                a: 1.0,
             },
-        ).await?;
+            polygon_sides: 5,
+            polygon_radius: 0.5,
+            vertex_color: [1.0, 0.0, 0.0],
+            camera: Camera::default(),
+        }
+    }
+}
+
+impl Scene {
+    /// Sets the number of polygon sides, clamped to `3..=`[`State::MAX_POLYGON_SIDES`];
+    /// fewer than three has no area.
+    pub fn set_polygon_sides(&mut self, sides: u16) {
+        self.polygon_sides = sides.clamp(3, State::MAX_POLYGON_SIDES);
+    }
+}
 
-        let config = Self::generate_config(&adapter, &surface, &canvas_size);
+type PendingGpu = Rc<RefCell<Option<Result<Gpu, StateError>>>>;
+
+/// The demo [`Renderer`]: a single regular polygon on a solid background.
+pub struct State {
+    canvas: Rc<leptos::web_sys::HtmlCanvasElement>,
+    gpu: Gpu,
+    // kept so a lost device is rebuilt the same way it was first created
+    config: GpuConfig,
+    // filled in by the task that rebuilds `gpu` after the device was lost
+    rebuild: Option<PendingGpu>,
+    // how many rebuilds have been started since the device was lost
+    rebuild_attempts: u32,
+    // set once rebuilding has been given up on, until the render loop takes it
+    device_error: Option<StateError>,
+    scene: Scene,
+    scale_factor: f64,
+    input: InputState,
+    actions: ActionMap,
+    controller: Box<dyn CameraController>,
+    // toggle: bool,
+}
 
-        // handle shaders
-        let clear_color = wgpu::Color {
-            r: 0.1,
-            g: 0.2,
-            b: 0.3,
-            a: 1.0,
-        };
+impl State {
+    /// How many times a lost device is recreated before the render loop is stopped.
+    pub const MAX_REBUILD_ATTEMPTS: u32 = 3;
+    /// The most sides the polygon can have. Far more than a circle needs at any canvas size,
+    /// and well within what the u16 index buffer can address.
+    pub const MAX_POLYGON_SIDES: u16 = 4096;
 
-        let shader = wgpu::include_wgsl!("./shaders/buffer_shader.wgsl");
-
-        // handle buffers
-        // aspect to generate regular polygon in canvas
-        let aspect = canvas_size.width as f32 / canvas_size.height as f32;
-        let polygon_buffer = PolygonBuffer::polygon_from_sides(&device, 5, 0.5, aspect);
-
-        // handle rendering
-        let render_pipeline = Self::generate_render_pipeline::<PolygonVertex>(
-    shader, 
-            &device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                label: Some("Render Pipeline Layout"),
-                bind_group_layouts: &[],
-                push_constant_ranges: &[],
-            }),
-            &device, 
-            &config
-        );
+    pub async fn new(canvas: Rc<leptos::web_sys::HtmlCanvasElement>, config: GpuConfig) -> Result<State, StateError> {
+        let scene = Scene::default();
+        let gpu = Gpu::new(canvas.clone(), &scene, &config).await?;
 
         Ok(Self {
-            surface,
-            device,
-            queue,
-            config,
-            is_surface_configured: false,
             canvas,
-            render_pipeline,
-            // challenge_render_pipeline,
-            clear_color,
+            gpu,
+            config,
+            rebuild: None,
+            rebuild_attempts: 0,
+            device_error: None,
+            scene,
+            scale_factor: 1.0,
+            input: InputState::default(),
+            actions: Self::default_actions(),
+            controller: Box::new(PanZoomController::default()),
             // toggle: false,
-            polygon_buffer,
         })
     }
 
-    fn generate_config(adapter: &wgpu::Adapter, surface: &wgpu::Surface, canvas_size: &PhysicalSize<u32>) -> wgpu::SurfaceConfiguration {
-        let surface_caps = surface.get_capabilities(adapter);
+    /// `reset_camera` on R and `toggle_projection` on P.
+    fn default_actions() -> ActionMap {
+        let mut actions = ActionMap::new();
+        actions
+            .bind("reset_camera", KeyCode::KeyCodeR)
+            .bind("toggle_projection", KeyCode::KeyCodeP);
+        actions
+    }
+
+    pub fn resize(&mut self, new_canvas: Rc<leptos::web_sys::HtmlCanvasElement>, scale_factor: f64) {
+        self.scale_factor = scale_factor;
 
-        let surface_format = surface_caps.formats.iter()
-            .find(|f| f.is_srgb())
-            .copied()
-            .unwrap_or(surface_caps.formats[0]);
+        if new_canvas.width() > 0 && new_canvas.height() > 0 {
+            self.canvas = new_canvas;
+            self.gpu.config.width = self.canvas.width();
+            self.gpu.config.height = self.canvas.height();
 
-        wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-            format: surface_format,
-            width: canvas_size.width,
-            height: canvas_size.height,
-            present_mode: surface_caps.present_modes[0],
-            alpha_mode: surface_caps.alpha_modes[0],
-            view_formats: vec![],
-            desired_maximum_frame_latency: 2,
+            self.gpu.surface.configure(&self.gpu.device, &self.gpu.config);
+            self.gpu.is_surface_configured = true;
+
+            let size = PhysicalSize {
+                width: self.gpu.config.width,
+                height: self.gpu.config.height,
+            };
+            self.scene.camera.aspect = size.aspect();
         }
     }
 
-    fn generate_render_pipeline<T: Vertex>(source: wgpu::ShaderModuleDescriptor, layout: &wgpu::PipelineLayout, device: &wgpu::Device, config: &wgpu::SurfaceConfiguration) -> wgpu::RenderPipeline {
-        let shader = device.create_shader_module(source);
-        
-        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Render Pipeline"),
-            layout: Some(layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_main"), // entry point in our wgsl code
-                buffers: &[
-                    T::desc(),
-                ], // any buffers we may require
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            },
-            fragment: Some(wgpu::FragmentState { // defining our fragment
-                module: &shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState { // defining the targets for our fragment
-                    format: config.format,
-                    blend: Some(wgpu::BlendState::REPLACE),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList, // defining our topology as a list of triangles
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw, // having our code read vertices CCW
-                cull_mode: Some(wgpu::Face::Back),
-                // Setting this to anything other than Fill requires Features::NON_FILL_POLYGON_MODE
-                polygon_mode: wgpu::PolygonMode::Fill,
-                // Requires Features::DEPTH_CLIP_CONTROL
-                unclipped_depth: false,
-                // Requires Features::CONSERVATIVE_RASTERIZATION
-                conservative: false,
-            },
-            // continued ...
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                count: 1,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            multiview: None,
-            cache: None,
-        })
+    /// The adapter that was picked, including which backend it runs on.
+    pub fn adapter_info(&self) -> &wgpu::AdapterInfo {
+        &self.gpu.adapter_info
     }
 
-    pub fn resize(&mut self, new_canvas: Arc<leptos::web_sys::HtmlCanvasElement>) {
-        if new_canvas.width() > 0 && new_canvas.height() > 0 {
-            self.canvas = new_canvas;
-            self.config.width = self.canvas.width();
-            self.config.height = self.canvas.height();
+    pub fn camera(&self) -> &Camera {
+        &self.scene.camera
+    }
 
-            self.surface.configure(&self.device, &self.config);
-            self.is_surface_configured = true;
+    /// The camera the next frame is drawn with; changes are picked up by [`State::update`].
+    pub fn camera_mut(&mut self) -> &mut Camera {
+        &mut self.scene.camera
+    }
 
-            // self.polygon_buffer.resize_polygon(&self.device, &self.canvas_size);
+    /// Replaces how the keyboard moves the camera, e.g. with an [`OrbitController`](crate::OrbitController).
+    pub fn set_camera_controller(&mut self, controller: impl CameraController + 'static) {
+        self.controller = Box::new(controller);
+    }
+
+    pub fn scene(&self) -> &Scene {
+        &self.scene
+    }
+
+    /// Takes over whatever `update` changes, rebuilding the polygon if its shape or color did.
+    pub fn apply_scene_update(&mut self, update: SceneUpdate) {
+        if let Some(clear_color) = update.clear_color {
+            self.scene.clear_color = clear_color;
+        }
+
+        let old = (self.scene.polygon_sides, self.scene.polygon_radius, self.scene.vertex_color);
+        if let Some(sides) = update.polygon_sides {
+            self.scene.set_polygon_sides(sides);
         }
+        if let Some(radius) = update.polygon_radius {
+            self.scene.polygon_radius = radius;
+        }
+        if let Some(color) = update.vertex_color {
+            self.scene.vertex_color = color;
+        }
+
+        // without a live device the rebuilt one picks the new shape up once it is swapped in
+        let changed = old != (self.scene.polygon_sides, self.scene.polygon_radius, self.scene.vertex_color);
+        if changed && self.rebuild.is_none() && !self.gpu.is_lost() {
+            self.rebuild_polygon();
+        }
+    }
+
+    fn rebuild_polygon(&mut self) {
+        self.gpu.polygon_buffer = PolygonBuffer::polygon_from_sides(
+            &self.gpu.device,
+            self.scene.polygon_sides,
+            self.scene.polygon_radius,
+            self.scene.vertex_color,
+        );
+    }
+
+    /// The `devicePixelRatio` the canvas was last sized with.
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// The limits the device was actually created with.
+    pub fn limits(&self) -> &wgpu::Limits {
+        &self.gpu.limits
     }
 
-    // # TODO: handle key
-    pub fn handle_key(&mut self, event: leptos::web_sys::KeyboardEvent) -> Result<(), <KeyCode as ::core::str::FromStr>::Err> {
-        let code = KeyCode::from_str(&event.key().to_ascii_lowercase()).unwrap_or(KeyCode::Unknown);
+    /// Marks the surface as unconfigured so the next [`State::render`] configures it again.
+    pub fn reconfigure(&mut self) {
+        self.gpu.is_surface_configured = false;
+    }
+
+    /// Destroys the current device as if the driver had reset, which exercises the same
+    /// recovery path as a real device loss.
+    #[cfg(test)]
+    fn simulate_device_loss(&self) {
+        self.gpu.device.destroy();
+    }
+
+    /// Swaps in a rebuilt [`Gpu`] once the device has been lost, keeping the scene as is.
+    /// Returns `true` while there is no usable device to render with.
+    ///
+    /// A failed rebuild is retried on the next frame, up to [`State::MAX_REBUILD_ATTEMPTS`]
+    /// times, after which the error is handed to the render loop through [`Renderer::take_error`].
+    fn recover_lost_device(&mut self) -> bool {
+        if let Some(pending) = &self.rebuild {
+            let result = pending.borrow_mut().take();
+            let Some(result) = result else {
+                // still waiting on the new device
+                return true;
+            };
+
+            self.rebuild = None;
+
+            match result {
+                Ok(gpu) => {
+                    leptos::logging::log!("Rebuilt GPU state after device loss");
+                    self.gpu = gpu;
+                    self.rebuild_attempts = 0;
+                    // the scene may have changed while the new device was being created
+                    self.rebuild_polygon();
+                    return false;
+                }
+                Err(e) => {
+                    leptos::logging::error!(
+                        "Failed to rebuild GPU state (attempt {} of {}): {e}",
+                        self.rebuild_attempts,
+                        Self::MAX_REBUILD_ATTEMPTS
+                    );
+                    if self.rebuild_attempts >= Self::MAX_REBUILD_ATTEMPTS {
+                        self.device_error = Some(StateError::DeviceLost {
+                            attempts: self.rebuild_attempts,
+                            last_error: Box::new(e),
+                        });
+                    }
+                    // the old device is still flagged as lost, so the next frame tries again
+                    return true;
+                }
+            }
+        }
+
+        if !self.gpu.is_lost() {
+            return false;
+        }
+        if self.rebuild_attempts >= Self::MAX_REBUILD_ATTEMPTS {
+            // given up on; the render loop stops once it has taken `device_error`
+            return true;
+        }
+
+        self.rebuild_attempts += 1;
+        let pending: PendingGpu = Rc::new(RefCell::new(None));
+        self.rebuild = Some(pending.clone());
 
-        // if needed can uncomment to handle the same key being pressed over and over
-        // if event.repeat() {
-        //     return Ok(());
+        let canvas = self.canvas.clone();
+        let scene = self.scene.clone();
+        let config = self.config.clone();
+        spawn_local(async move {
+            let gpu = Gpu::new(canvas, &scene, &config).await;
+            *pending.borrow_mut() = Some(gpu);
+        });
+
+        true
+    }
+
+    pub fn handle_key(&mut self, event: KeyEvent) {
+        // if let KeyCode::KeyCodeSpace = event.physical {
+        //     self.toggle = !self.toggle;
         // }
 
-        match code {
-            // KeyCode::KeyCodeSpace => {
-            //     self.toggle = !self.toggle;
-            // },
-            _ => {
-                leptos::logging::log!("{:?}", code);
-                // self.camera_controller.process_events(&code, true);
+        self.input.set_modifiers(event.modifiers);
+        self.input.press(event.physical, event.repeat);
+    }
+
+    pub fn handle_key_up(&mut self, event: KeyEvent) {
+        self.input.set_modifiers(event.modifiers);
+        self.input.release(event.physical);
+    }
+
+    /// The page lost focus, so any held keys will never see their `keyup`.
+    pub fn handle_blur(&mut self) {
+        self.input.release_all();
+    }
+
+    pub fn handle_pointer_down(&mut self, event: PointerEvent) {
+        self.input.pointer_down(&event);
+    }
+
+    pub fn handle_pointer_up(&mut self, event: PointerEvent) {
+        self.input.pointer_up(&event);
+    }
+
+    pub fn handle_pointer_cancel(&mut self, event: PointerEvent) {
+        self.input.pointer_cancel(&event);
+    }
+
+    pub fn handle_pointer_move(&mut self, event: PointerEvent) {
+        self.input.pointer_move(&event);
+    }
+
+    pub fn handle_wheel(&mut self, event: WheelEvent) {
+        self.input.wheel(&event);
+    }
+
+    pub fn handle_pointer_lock(&mut self, locked: bool) {
+        self.input.set_pointer_locked(locked);
+    }
+
+    pub fn handle_gamepads(&mut self, gamepads: &[GamepadSnapshot]) {
+        self.input.update_gamepads(gamepads);
+    }
+
+    pub fn handle_gamepad_connection(&mut self, event: GamepadConnection) {
+        let verb = if event.connected { "connected" } else { "disconnected" };
+        leptos::logging::log!("Gamepad {} {verb}: {}", event.index, event.id);
+        self.input.gamepad_connection(&event);
+    }
+
+    pub fn handle_action(&mut self, event: ActionEvent) {
+        if !event.pressed || event.repeat {
+            return;
+        }
+
+        match event.action.as_str() {
+            "reset_camera" => self.reset_camera(),
+            "toggle_projection" => {
+                self.scene.camera.mode = match self.scene.camera.mode {
+                    CameraMode::Orthographic => CameraMode::Perspective,
+                    CameraMode::Perspective => CameraMode::Orthographic,
+                };
             }
+            _ => {}
         }
+    }
 
-        Ok(())
+    /// Puts the camera back where it started, keeping its projection.
+    pub fn reset_camera(&mut self) {
+        self.scene.camera = Camera {
+            aspect: self.scene.camera.aspect,
+            mode: self.scene.camera.mode,
+            ..Camera::default()
+        };
+    }
+
+    pub fn action_map(&self) -> &ActionMap {
+        &self.actions
+    }
+
+    /// The bindings to rebind at runtime, e.g. from a settings menu or a loaded config file.
+    pub fn action_map_mut(&mut self) -> &mut ActionMap {
+        &mut self.actions
+    }
+
+    /// The keyboard, pointer and gamepad state for the current frame.
+    pub fn input(&self) -> &InputState {
+        &self.input
     }
     
-    // # TODO: update
-    pub fn update(&mut self) {
+    pub fn update(&mut self, time: FrameTime) {
+        // the touch equivalent of the `reset_camera` action
+        if self.input.gestures().iter().any(|gesture| matches!(gesture, Gesture::DoubleTap { .. })) {
+            self.reset_camera();
+        }
 
+        self.controller.update_camera(&mut self.scene.camera, &self.input, time.delta);
+        self.input.end_frame();
+
+        if self.recover_lost_device() {
+            return;
+        }
+
+        self.gpu.queue.write_buffer(
+            &self.gpu.camera_buffer,
+            0,
+            bytemuck::cast_slice(&[CameraUniform::from_camera(&self.scene.camera)]),
+        );
     }
 
     pub fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
         // self.canvas.as_ref().
 
-        if !self.is_surface_configured {
-            self.surface.configure(&self.device, &self.config);
-            self.is_surface_configured = true;
+        if self.rebuild.is_some() || self.gpu.is_lost() {
+            // nothing to draw with until the device has been rebuilt
+            return Ok(());
         }
 
-        let output = self.surface.get_current_texture()?;
+        let gpu = &mut self.gpu;
+
+        if !gpu.is_surface_configured {
+            gpu.surface.configure(&gpu.device, &gpu.config);
+            gpu.is_surface_configured = true;
+        }
+
+        let output = gpu.surface.get_current_texture()?;
         let view = output.texture.create_view(&wgpu::TextureViewDescriptor::default());
 
-        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("Render Encoder"),
         });
 
@@ -228,7 +417,7 @@ impl<'a> State<'a> {
                     view: &view,
                     resolve_target: None,
                     ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(self.clear_color),
+                        load: wgpu::LoadOp::Clear(self.scene.clear_color),
                         store: wgpu::StoreOp::Store,
                     },
                 })],
@@ -237,32 +426,194 @@ impl<'a> State<'a> {
                 timestamp_writes: None,
             });
 
-            render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_pipeline(&gpu.render_pipeline);
+            render_pass.set_bind_group(0, &gpu.camera_bind_group, &[]);
 
-            render_pass.set_vertex_buffer(0, self.polygon_buffer.vertex_buffer.slice(..));
-            render_pass.set_index_buffer(self.polygon_buffer.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
-            render_pass.draw_indexed(0..self.polygon_buffer.num_indices, 0, 0..1);
+            render_pass.set_vertex_buffer(0, gpu.polygon_buffer.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(gpu.polygon_buffer.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+            render_pass.draw_indexed(0..gpu.polygon_buffer.num_indices, 0, 0..1);
         }
 
-        self.queue.submit([encoder.finish()]);
+        gpu.queue.submit([encoder.finish()]);
         output.present();
 
         Ok(())
     }
 }
 
-// challenge impls
-// impl<'a> State<'a> {
-//     pub fn mouse_challenge(&mut self, loc: PhysicalSize<u32>) {
-//         let x = loc.width as f64 / self.canvas_size.width as f64;
-//         let y = loc.height as f64 / self.canvas_size.height as f64;
-//         let z = (loc.width + loc.height) as f64 / (self.canvas_size.width + self.canvas_size.height) as f64;
-
-//         self.clear_color = wgpu::Color {
-//             r: x,
-//             g: y,
-//             b: z,
-//             a: 1.0,
-//         }
-//     }
-// }
\ No newline at end of file
+impl Renderer for State {
+    type Error = StateError;
+    type Config = GpuConfig;
+    type Params = SceneUpdate;
+
+    async fn init(canvas: Rc<leptos::web_sys::HtmlCanvasElement>, config: GpuConfig) -> Result<Self, StateError> {
+        State::new(canvas, config).await
+    }
+
+    fn update(&mut self, time: FrameTime) {
+        State::update(self, time)
+    }
+
+    fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        State::render(self)
+    }
+
+    fn resize(&mut self, canvas: Rc<leptos::web_sys::HtmlCanvasElement>, scale_factor: f64) {
+        State::resize(self, canvas, scale_factor)
+    }
+
+    fn reconfigure(&mut self, _canvas: Rc<leptos::web_sys::HtmlCanvasElement>) {
+        State::reconfigure(self)
+    }
+
+    fn max_texture_dimension(&self) -> u32 {
+        self.gpu.limits.max_texture_dimension_2d
+    }
+
+    fn adapter_info(&self) -> Option<&wgpu::AdapterInfo> {
+        Some(State::adapter_info(self))
+    }
+
+    fn take_error(&mut self) -> Option<StateError> {
+        self.device_error.take()
+    }
+
+    fn wants_redraw(&self) -> bool {
+        // a lost device is rebuilt from `update`, which only runs while frames are drawn
+        let retrying = self.gpu.is_lost() && self.rebuild_attempts < Self::MAX_REBUILD_ATTEMPTS;
+        self.input.is_active() || self.rebuild.is_some() || retrying || self.device_error.is_some()
+    }
+
+    fn set_params(&mut self, params: SceneUpdate) {
+        State::apply_scene_update(self, params)
+    }
+
+    fn handle_key(&mut self, event: KeyEvent) {
+        State::handle_key(self, event)
+    }
+
+    fn handle_key_up(&mut self, event: KeyEvent) {
+        State::handle_key_up(self, event)
+    }
+
+    fn handle_pointer_down(&mut self, event: PointerEvent) {
+        State::handle_pointer_down(self, event)
+    }
+
+    fn handle_pointer_up(&mut self, event: PointerEvent) {
+        State::handle_pointer_up(self, event)
+    }
+
+    fn handle_pointer_cancel(&mut self, event: PointerEvent) {
+        State::handle_pointer_cancel(self, event)
+    }
+
+    fn handle_pointer_move(&mut self, event: PointerEvent) {
+        State::handle_pointer_move(self, event)
+    }
+
+    fn handle_wheel(&mut self, event: WheelEvent) {
+        State::handle_wheel(self, event)
+    }
+
+    fn handle_pointer_lock(&mut self, locked: bool) {
+        State::handle_pointer_lock(self, locked)
+    }
+
+    fn handle_gamepads(&mut self, gamepads: &[GamepadSnapshot]) {
+        State::handle_gamepads(self, gamepads)
+    }
+
+    fn handle_gamepad_connection(&mut self, event: GamepadConnection) {
+        State::handle_gamepad_connection(self, event)
+    }
+
+    fn action_map(&self) -> Option<&ActionMap> {
+        Some(&self.actions)
+    }
+
+    fn handle_action(&mut self, event: ActionEvent) {
+        State::handle_action(self, event)
+    }
+
+    fn handle_blur(&mut self) {
+        State::handle_blur(self)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use leptos::wasm_bindgen::JsCast;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::{Scene, State};
+    use crate::utils::gpu::GpuConfig;
+    use crate::utils::helpers::testing::sleep;
+    use crate::utils::types::scene_update::SceneUpdate;
+
+    #[wasm_bindgen_test]
+    fn polygon_sides_are_clamped() {
+        let mut scene = Scene::default();
+
+        scene.set_polygon_sides(State::MAX_POLYGON_SIDES);
+        assert_eq!(scene.polygon_sides, State::MAX_POLYGON_SIDES);
+        scene.set_polygon_sides(State::MAX_POLYGON_SIDES + 1);
+        assert_eq!(scene.polygon_sides, State::MAX_POLYGON_SIDES);
+        scene.set_polygon_sides(u16::MAX);
+        assert_eq!(scene.polygon_sides, State::MAX_POLYGON_SIDES);
+
+        scene.set_polygon_sides(3);
+        assert_eq!(scene.polygon_sides, 3);
+        scene.set_polygon_sides(0);
+        assert_eq!(scene.polygon_sides, 3);
+    }
+
+    // unlike the rest of the suite this needs a browser with a working WebGPU or WebGL2 device
+    async fn state() -> State {
+        let document = leptos::web_sys::window().unwrap().document().unwrap();
+        let canvas: leptos::web_sys::HtmlCanvasElement = document.create_element("canvas").unwrap().unchecked_into();
+        canvas.set_width(64);
+        canvas.set_height(64);
+        State::new(Rc::new(canvas), GpuConfig::default()).await.expect("no GPU to test with")
+    }
+
+    #[wasm_bindgen_test]
+    async fn recovers_from_simulated_device_loss() {
+        let mut state = state().await;
+        let clear_color = wgpu::Color::BLACK;
+        state.apply_scene_update(SceneUpdate {
+            clear_color: Some(clear_color),
+            polygon_sides: Some(7),
+            ..SceneUpdate::default()
+        });
+
+        state.simulate_device_loss();
+        // the device lost callback fires asynchronously
+        for _ in 0..100 {
+            if state.gpu.is_lost() {
+                break;
+            }
+            sleep(10).await;
+        }
+        assert!(state.gpu.is_lost());
+
+        for _ in 0..100 {
+            if !state.recover_lost_device() {
+                break;
+            }
+            sleep(10).await;
+        }
+
+        assert!(!state.gpu.is_lost(), "no new device was swapped in");
+        assert!(state.rebuild.is_none());
+        assert_eq!(state.rebuild_attempts, 0);
+        assert_eq!(state.scene.clear_color, clear_color);
+        assert_eq!(state.scene.polygon_sides, 7);
+        // the polygon was rebuilt on the new device from the kept scene, as a fan of n - 2 triangles
+        assert_eq!(state.gpu.polygon_buffer.num_indices, (7 - 2) * 3);
+        assert!(state.render().is_ok());
+    }
+}
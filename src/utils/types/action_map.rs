@@ -0,0 +1,333 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::utils::types::action_map_error::ActionMapError;
+use crate::utils::types::key_event::{KeyEvent, Modifiers};
+use crate::utils::types::keycode::KeyCode;
+
+const MODIFIER_NAMES: [(Modifiers, &str); 4] = [
+    (Modifiers::CONTROL, "Control"),
+    (Modifiers::ALT, "Alt"),
+    (Modifiers::SHIFT, "Shift"),
+    (Modifiers::META, "Meta"),
+];
+
+/// A physical key plus the exact modifiers that have to be held with it, written as e.g.
+/// `"Control+Shift+KeyZ"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub key: KeyCode,
+    pub modifiers: Modifiers,
+}
+
+impl KeyChord {
+    pub fn new(key: KeyCode, modifiers: Modifiers) -> Self {
+        Self { key, modifiers }
+    }
+
+    /// Whether `event` triggers this chord; extra or missing modifiers do not match. A modifier
+    /// key sets its own modifier while going down, so that one is left out of the comparison
+    /// and e.g. a plain `ShiftLeft` chord still fires.
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        let own = Modifiers::of_key(self.key);
+        self.key == event.physical && self.modifiers - own == event.modifiers - own
+    }
+}
+
+impl From<KeyCode> for KeyChord {
+    fn from(key: KeyCode) -> Self {
+        Self::new(key, Modifiers::empty())
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in MODIFIER_NAMES {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = ActionMapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts: Vec<&str> = s.split('+').map(str::trim).collect();
+        let key = parts.pop().unwrap_or_default();
+        let key = match KeyCode::from_code(key) {
+            KeyCode::Unknown => return Err(ActionMapError::UnknownKey(key.to_string())),
+            key => key,
+        };
+
+        let mut modifiers = Modifiers::empty();
+        for part in parts {
+            let Some((modifier, _)) = MODIFIER_NAMES.iter().find(|(_, name)| name.eq_ignore_ascii_case(part)) else {
+                return Err(ActionMapError::UnknownModifier(part.to_string()));
+            };
+            modifiers |= *modifier;
+        }
+
+        Ok(Self { key, modifiers })
+    }
+}
+
+impl Serialize for KeyChord {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyChord {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// A named action being triggered or released, as handed to
+/// [`Renderer::handle_action`](crate::Renderer::handle_action).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionEvent {
+    pub action: String,
+    /// `true` on `keydown`, `false` on `keyup`.
+    pub pressed: bool,
+    /// Whether this is an auto-repeat of a chord that is being held down.
+    pub repeat: bool,
+}
+
+/// The actions whose chords are currently held down, by the key that pressed them, so a
+/// `keyup` releases only what its `keydown` triggered.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ActiveActions {
+    held: HashMap<KeyCode, Vec<String>>,
+}
+
+impl ActiveActions {
+    /// Records `action` as held by `key`. Returns `false` if it already was, e.g. on auto-repeat.
+    pub fn press(&mut self, key: KeyCode, action: &str) -> bool {
+        let actions = self.held.entry(key).or_default();
+        if actions.iter().any(|held| held == action) {
+            return false;
+        }
+        actions.push(action.to_string());
+        true
+    }
+
+    /// The actions let go by releasing `key`, leaving out any still held through another key.
+    pub fn release(&mut self, key: KeyCode) -> Vec<String> {
+        let mut released = self.held.remove(&key).unwrap_or_default();
+        released.retain(|action| !self.held.values().flatten().any(|held| held == action));
+        released
+    }
+
+    /// Every held action, e.g. on `blur`, when the matching `keyup`s will never arrive.
+    pub fn release_all(&mut self) -> Vec<String> {
+        let mut released: Vec<String> = self.held.drain().flat_map(|(_, actions)| actions).collect();
+        released.sort();
+        released.dedup();
+        released
+    }
+}
+
+/// Binds key chords to named actions such as `"zoom_in"` or `"toggle_wireframe"`, so
+/// handlers match on what the user meant instead of on raw [`KeyCode`]s.
+///
+/// Serialized as a table from action name to its chords:
+///
+/// ```toml
+/// reset_camera = ["KeyR"]
+/// undo = ["Control+KeyZ", "Meta+KeyZ"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ActionMap {
+    bindings: BTreeMap<String, Vec<KeyChord>>,
+}
+
+impl ActionMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `chord` to `action`, taking it away from whichever action had it before.
+    pub fn bind(&mut self, action: impl Into<String>, chord: impl Into<KeyChord>) -> &mut Self {
+        let chord = chord.into();
+        self.unbind_chord(chord);
+        self.bindings.entry(action.into()).or_default().push(chord);
+        self
+    }
+
+    /// Replaces every chord of `action`, e.g. after the user picked a new key in a settings menu.
+    pub fn rebind(&mut self, action: impl Into<String>, chords: impl IntoIterator<Item = KeyChord>) -> &mut Self {
+        let action = action.into();
+        self.bindings.remove(&action);
+        for chord in chords {
+            self.bind(action.clone(), chord);
+        }
+        self
+    }
+
+    /// Removes `action` and all of its chords.
+    pub fn unbind(&mut self, action: &str) -> Option<Vec<KeyChord>> {
+        self.bindings.remove(action)
+    }
+
+    /// Frees `chord` from whichever action it was bound to.
+    pub fn unbind_chord(&mut self, chord: KeyChord) {
+        self.bindings.retain(|_, chords| {
+            chords.retain(|bound| *bound != chord);
+            !chords.is_empty()
+        });
+    }
+
+    /// The chords bound to `action`, if any.
+    pub fn chords(&self, action: &str) -> &[KeyChord] {
+        self.bindings.get(action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Every action with its chords, ordered by action name.
+    pub fn bindings(&self) -> impl Iterator<Item = (&str, &[KeyChord])> {
+        self.bindings.iter().map(|(action, chords)| (action.as_str(), chords.as_slice()))
+    }
+
+    /// The action a `keydown` triggers, matching its modifiers exactly.
+    pub fn resolve(&self, event: &KeyEvent) -> Option<&str> {
+        self.bindings
+            .iter()
+            .find(|(_, chords)| chords.iter().any(|chord| chord.matches(event)))
+            .map(|(action, _)| action.as_str())
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, ActionMapError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_json(&self) -> Result<String, ActionMapError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_toml(toml: &str) -> Result<Self, ActionMapError> {
+        Ok(toml::from_str(toml)?)
+    }
+
+    pub fn to_toml(&self) -> Result<String, ActionMapError> {
+        Ok(toml::to_string(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::{ActionMap, ActiveActions, KeyChord};
+    use crate::utils::types::action_map_error::ActionMapError;
+    use crate::utils::types::key_event::{KeyEvent, Modifiers};
+    use crate::utils::types::keycode::KeyCode;
+
+    fn keydown(key: KeyCode, modifiers: Modifiers) -> KeyEvent {
+        KeyEvent {
+            physical: key,
+            logical: key,
+            modifiers,
+            repeat: false,
+        }
+    }
+
+    fn bindings() -> ActionMap {
+        let mut actions = ActionMap::new();
+        actions
+            .bind("reset_camera", KeyCode::KeyCodeR)
+            .bind("undo", KeyChord::new(KeyCode::KeyCodeZ, Modifiers::CONTROL))
+            .bind("undo", KeyChord::new(KeyCode::KeyCodeZ, Modifiers::META))
+            .bind("redo", KeyChord::new(KeyCode::KeyCodeZ, Modifiers::CONTROL | Modifiers::SHIFT));
+        actions
+    }
+
+    #[wasm_bindgen_test]
+    fn round_trips_through_json() {
+        let actions = bindings();
+        let json = actions.to_json().unwrap();
+        assert!(json.contains("\"Control+Shift+KeyZ\""), "{json}");
+        assert_eq!(ActionMap::from_json(&json).unwrap(), actions);
+    }
+
+    #[wasm_bindgen_test]
+    fn round_trips_through_toml() {
+        let actions = bindings();
+        let toml = actions.to_toml().unwrap();
+        assert!(toml.contains("\"Control+Shift+KeyZ\""), "{toml}");
+        assert_eq!(ActionMap::from_toml(&toml).unwrap(), actions);
+
+        let written = ActionMap::from_toml("undo = [\"Control+KeyZ\", \"Meta+KeyZ\"]").unwrap();
+        assert_eq!(written.chords("undo"), bindings().chords("undo"));
+    }
+
+    #[wasm_bindgen_test]
+    fn rejects_unknown_keys_and_modifiers() {
+        let error = ActionMap::from_json(r#"{ "jump": ["Control+Spacebar"] }"#).unwrap_err();
+        assert!(matches!(error, ActionMapError::Json(_)), "{error:?}");
+        assert!(error.to_string().contains("Spacebar"), "{error}");
+
+        let error = ActionMap::from_toml(r#"jump = ["Spacebar"]"#).unwrap_err();
+        assert!(matches!(error, ActionMapError::TomlDe(_)), "{error:?}");
+        assert!(error.to_string().contains("Spacebar"), "{error}");
+
+        assert!(matches!("Hyper+KeyZ".parse::<KeyChord>(), Err(ActionMapError::UnknownModifier(_))));
+        assert!(matches!("Control+".parse::<KeyChord>(), Err(ActionMapError::UnknownKey(_))));
+    }
+
+    #[wasm_bindgen_test]
+    fn modifier_key_chords_ignore_their_own_modifier() {
+        // the browser already reports shift as held on the shift key's own keydown
+        let sprint = KeyChord::from(KeyCode::KeyCodeShiftLeft);
+        assert!(sprint.matches(&keydown(KeyCode::KeyCodeShiftLeft, Modifiers::SHIFT)));
+        assert!(!sprint.matches(&keydown(KeyCode::KeyCodeShiftLeft, Modifiers::SHIFT | Modifiers::CONTROL)));
+
+        let undo: KeyChord = "Control+KeyZ".parse().unwrap();
+        assert!(undo.matches(&keydown(KeyCode::KeyCodeZ, Modifiers::CONTROL)));
+        assert!(!undo.matches(&keydown(KeyCode::KeyCodeZ, Modifiers::empty())));
+        assert!(!undo.matches(&keydown(KeyCode::KeyCodeZ, Modifiers::CONTROL | Modifiers::SHIFT)));
+    }
+
+    #[wasm_bindgen_test]
+    fn resolves_a_plain_modifier_binding() {
+        let mut actions = ActionMap::new();
+        actions.bind("sprint", KeyCode::KeyCodeShiftLeft);
+
+        assert_eq!(actions.resolve(&keydown(KeyCode::KeyCodeShiftLeft, Modifiers::SHIFT)), Some("sprint"));
+    }
+
+    #[wasm_bindgen_test]
+    fn only_releases_actions_that_were_pressed() {
+        let mut active = ActiveActions::default();
+        assert!(active.release(KeyCode::KeyCodeZ).is_empty());
+
+        assert!(active.press(KeyCode::KeyCodeZ, "undo"));
+        // auto-repeat doesn't press it again
+        assert!(!active.press(KeyCode::KeyCodeZ, "undo"));
+        assert_eq!(active.release(KeyCode::KeyCodeZ), ["undo"]);
+        assert!(active.release(KeyCode::KeyCodeZ).is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn keeps_an_action_held_by_another_key() {
+        let mut active = ActiveActions::default();
+        active.press(KeyCode::KeyCodeW, "forward");
+        active.press(KeyCode::KeyCodeArrowUp, "forward");
+
+        assert!(active.release(KeyCode::KeyCodeW).is_empty());
+        assert_eq!(active.release(KeyCode::KeyCodeArrowUp), ["forward"]);
+
+        active.press(KeyCode::KeyCodeW, "forward");
+        active.press(KeyCode::KeyCodeArrowUp, "forward");
+        active.press(KeyCode::KeyCodeR, "reset_camera");
+        assert_eq!(active.release_all(), ["forward", "reset_camera"]);
+        assert!(active.release_all().is_empty());
+    }
+}
@@ -0,0 +1,54 @@
+use std::fmt;
+
+/// Reasons an [`ActionMap`](crate::ActionMap) or one of its chords could not be read or written.
+#[derive(Debug)]
+pub enum ActionMapError {
+    /// A chord named a key that [`KeyCode`](crate::KeyCode) does not know.
+    UnknownKey(String),
+    /// A chord used a modifier other than `Control`, `Alt`, `Shift` or `Meta`.
+    UnknownModifier(String),
+    Json(serde_json::Error),
+    TomlDe(toml::de::Error),
+    TomlSer(toml::ser::Error),
+}
+
+impl fmt::Display for ActionMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ActionMapError::UnknownKey(key) => write!(f, "Unknown key in binding: {key:?}"),
+            ActionMapError::UnknownModifier(modifier) => write!(f, "Unknown modifier in binding: {modifier:?}"),
+            ActionMapError::Json(e) => write!(f, "Invalid JSON action map: {e}"),
+            ActionMapError::TomlDe(e) => write!(f, "Invalid TOML action map: {e}"),
+            ActionMapError::TomlSer(e) => write!(f, "Could not write action map as TOML: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ActionMapError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ActionMapError::Json(e) => Some(e),
+            ActionMapError::TomlDe(e) => Some(e),
+            ActionMapError::TomlSer(e) => Some(e),
+            ActionMapError::UnknownKey(_) | ActionMapError::UnknownModifier(_) => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for ActionMapError {
+    fn from(e: serde_json::Error) -> Self {
+        ActionMapError::Json(e)
+    }
+}
+
+impl From<toml::de::Error> for ActionMapError {
+    fn from(e: toml::de::Error) -> Self {
+        ActionMapError::TomlDe(e)
+    }
+}
+
+impl From<toml::ser::Error> for ActionMapError {
+    fn from(e: toml::ser::Error) -> Self {
+        ActionMapError::TomlSer(e)
+    }
+}
@@ -0,0 +1,17 @@
+use crate::utils::types::camera::Camera;
+
+/// The view-projection matrix bound at `@group(0) @binding(0)` in `buffer_shader.wgsl`,
+/// mapping world units to clip space.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct CameraUniform {
+    pub view_proj: [[f32; 4]; 4],
+}
+
+impl CameraUniform {
+    pub fn from_camera(camera: &Camera) -> Self {
+        Self {
+            view_proj: camera.build_view_projection_matrix().into(),
+        }
+    }
+}
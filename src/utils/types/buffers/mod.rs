@@ -1,10 +1,72 @@
+pub mod camera_uniform;
 pub mod polygon_buffer;
 pub mod polygon_vertex;
 
-pub trait Vertex: Sized {
-    const ATTRIBUTES: [wgpu::VertexAttribute; 2];
-    fn desc() -> wgpu::VertexBufferLayout<'static>;
-    fn gen_polygon(sides: u16, radius: f32, aspect: f32) -> (Vec<Self>, Vec<u16>);
+/// Describes how a `#[repr(C)]` vertex struct is laid out in a vertex buffer. Any number of
+/// attributes (positions, normals, UVs, tangents, ...) can be listed; see [`vertex_attributes!`].
+pub trait Vertex: bytemuck::Pod {
+    /// One attribute per field handed to the shader, in any order.
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute];
+    /// Whether the buffer advances per vertex or per instance.
+    const STEP_MODE: wgpu::VertexStepMode = wgpu::VertexStepMode::Vertex;
+
+    fn desc() -> wgpu::VertexBufferLayout<'static> {
+        debug_assert!(
+            layout_fits::<Self>(),
+            "vertex attributes of {} overlap or run past the end of the struct",
+            std::any::type_name::<Self>()
+        );
+
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as wgpu::BufferAddress,
+            step_mode: Self::STEP_MODE,
+            attributes: Self::ATTRIBUTES,
+        }
+    }
+}
+
+/// Geometry that can be generated as a regular polygon, separate from how its vertices are
+/// laid out so other shapes can reuse the same [`Vertex`] types.
+pub trait PolygonGeometry: Vertex {
+    fn gen_polygon(sides: u16, radius: f32, color: [f32; 3]) -> (Vec<Self>, Vec<u16>);
+}
+
+/// Whether every attribute of `T` lies inside the struct without overlapping another one.
+pub fn layout_fits<T: Vertex>() -> bool {
+    let mut ranges: Vec<_> = T::ATTRIBUTES
+        .iter()
+        .map(|attribute| (attribute.offset, attribute.offset + attribute.format.size()))
+        .collect();
+    ranges.sort_unstable();
+
+    let inside = ranges.iter().all(|(_, end)| *end <= std::mem::size_of::<T>() as wgpu::BufferAddress);
+    let disjoint = ranges.windows(2).all(|pair| pair[0].1 <= pair[1].0);
+
+    inside && disjoint
+}
+
+/// Builds [`Vertex::ATTRIBUTES`] from a struct's field names, taking each offset from
+/// `offset_of!` so it always matches the actual `#[repr(C)]` layout. Paths go through this
+/// crate's `wgpu` re-export, so callers don't need `wgpu` as a dependency of their own:
+///
+/// ```ignore
+/// const ATTRIBUTES: &'static [wgpu::VertexAttribute] = vertex_attributes!(MeshVertex {
+///     position => 0: Float32x3,
+///     normal => 1: Float32x3,
+///     uv => 2: Float32x2,
+/// });
+/// ```
+#[macro_export]
+macro_rules! vertex_attributes {
+    ($ty:ty { $($field:ident => $location:literal : $format:ident),* $(,)? }) => {
+        &[$(
+            $crate::wgpu::VertexAttribute {
+                offset: ::core::mem::offset_of!($ty, $field) as $crate::wgpu::BufferAddress,
+                shader_location: $location,
+                format: $crate::wgpu::VertexFormat::$format,
+            },
+        )*]
+    };
 }
 
 // use polygon_vertex::PolygonVertex;
@@ -51,3 +113,109 @@ pub trait Vertex: Sized {
 // const INDICES: &[u16] = &[0, 1, 4, 1, 2, 4, 2, 3, 4];
 
 // pub const NUM_VERTICES: u32 = VERTICES.len() as u32;
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::{Vertex, layout_fits};
+
+    #[repr(C)]
+    #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+    struct MeshVertex {
+        position: [f32; 3],
+        normal: [f32; 3],
+        uv: [f32; 2],
+        tangent: [f32; 4],
+    }
+
+    impl Vertex for MeshVertex {
+        const ATTRIBUTES: &'static [wgpu::VertexAttribute] = vertex_attributes!(MeshVertex {
+            position => 0: Float32x3,
+            normal => 1: Float32x3,
+            uv => 2: Float32x2,
+            tangent => 3: Float32x4,
+        });
+    }
+
+    // Pod rules out implicit padding, so the gap before `tangent` is spelled out and left unbound
+    #[repr(C)]
+    #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+    struct PaddedVertex {
+        position: [f32; 3],
+        _padding: u32,
+        tangent: [f32; 4],
+    }
+
+    impl Vertex for PaddedVertex {
+        const ATTRIBUTES: &'static [wgpu::VertexAttribute] = vertex_attributes!(PaddedVertex {
+            position => 0: Float32x3,
+            tangent => 1: Float32x4,
+        });
+    }
+
+    // same layout as `MeshVertex`, with the normal starting inside the position
+    #[repr(C)]
+    #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+    struct OverlappingVertex(MeshVertex);
+
+    impl Vertex for OverlappingVertex {
+        const ATTRIBUTES: &'static [wgpu::VertexAttribute] = &[
+            wgpu::VertexAttribute {
+                offset: 0,
+                shader_location: 0,
+                format: wgpu::VertexFormat::Float32x3,
+            },
+            wgpu::VertexAttribute {
+                offset: 8,
+                shader_location: 1,
+                format: wgpu::VertexFormat::Float32x3,
+            },
+        ];
+    }
+
+    // same layout as `MeshVertex`, with the tangent reaching past the end of the struct
+    #[repr(C)]
+    #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+    struct OverflowingVertex(MeshVertex);
+
+    impl Vertex for OverflowingVertex {
+        const ATTRIBUTES: &'static [wgpu::VertexAttribute] = &[wgpu::VertexAttribute {
+            offset: 36,
+            shader_location: 0,
+            format: wgpu::VertexFormat::Float32x4,
+        }];
+    }
+
+    fn offsets<T: Vertex>() -> Vec<wgpu::BufferAddress> {
+        T::ATTRIBUTES.iter().map(|attribute| attribute.offset).collect()
+    }
+
+    #[wasm_bindgen_test]
+    fn attributes_follow_the_field_offsets() {
+        assert_eq!(offsets::<MeshVertex>(), [0, 12, 24, 32]);
+        assert_eq!(
+            MeshVertex::ATTRIBUTES.iter().map(|attribute| attribute.shader_location).collect::<Vec<_>>(),
+            [0, 1, 2, 3]
+        );
+
+        let layout = MeshVertex::desc();
+        assert_eq!(layout.array_stride, 48);
+        assert!(layout_fits::<MeshVertex>());
+    }
+
+    #[wasm_bindgen_test]
+    fn padding_is_skipped_but_counted_in_the_stride() {
+        assert_eq!(offsets::<PaddedVertex>(), [0, 16]);
+
+        let layout = PaddedVertex::desc();
+        assert_eq!(layout.array_stride, 32);
+        assert!(layout_fits::<PaddedVertex>());
+    }
+
+    #[wasm_bindgen_test]
+    fn overlapping_or_overflowing_attributes_do_not_fit() {
+        assert!(!layout_fits::<OverlappingVertex>());
+        assert!(!layout_fits::<OverflowingVertex>());
+    }
+}
@@ -2,9 +2,9 @@ use std::marker::PhantomData;
 
 use wgpu::{util::DeviceExt, Device};
 
-use crate::utils::types::buffers::Vertex;
+use crate::utils::types::buffers::{PolygonGeometry, Vertex};
 
-pub struct PolygonBuffer<T: bytemuck::Pod + bytemuck::Zeroable + Vertex> {
+pub struct PolygonBuffer<T: Vertex> {
     // check macro kata to make stuff like this more readable
     pub vertex_buffer: wgpu::Buffer,
     pub index_buffer: wgpu::Buffer,
@@ -12,7 +12,7 @@ pub struct PolygonBuffer<T: bytemuck::Pod + bytemuck::Zeroable + Vertex> {
     _marker: PhantomData<T>,
 }
 
-impl<T: bytemuck::Pod + bytemuck::Zeroable + Vertex> PolygonBuffer<T> {
+impl<T: Vertex> PolygonBuffer<T> {
     pub fn new(device: &Device, vertices: &[T], indices: &[u16]) -> Self {
         let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Vertex Buffer"),
@@ -36,8 +36,11 @@ impl<T: bytemuck::Pod + bytemuck::Zeroable + Vertex> PolygonBuffer<T> {
         }
     }
 
-    pub fn polygon_from_sides(device: &Device, num_sides: u16, radius: f32, aspect: f32) -> Self {
-        let (vertices, indices) = T::gen_polygon(num_sides, radius, aspect);
+}
+
+impl<T: PolygonGeometry> PolygonBuffer<T> {
+    pub fn polygon_from_sides(device: &Device, num_sides: u16, radius: f32, color: [f32; 3]) -> Self {
+        let (vertices, indices) = T::gen_polygon(num_sides, radius, color);
 
         Self::new(device, &vertices, &indices)
     }
@@ -1,4 +1,5 @@
-use crate::utils::types::buffers::Vertex;
+use crate::utils::types::buffers::{PolygonGeometry, Vertex};
+use crate::vertex_attributes;
 
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
@@ -8,52 +9,69 @@ pub struct PolygonVertex {
 }
 
 impl Vertex for PolygonVertex {
-    // shorthand macro to do what is down in the commented out attributes code
-    const ATTRIBUTES: [wgpu::VertexAttribute; 2] =
-        wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3];
-
-    fn desc() -> wgpu::VertexBufferLayout<'static> {
-        wgpu::VertexBufferLayout {
-            array_stride: std::mem::size_of::<PolygonVertex>() as wgpu::BufferAddress, // define how wide the vertex is within our memory
-            step_mode: wgpu::VertexStepMode::Vertex, // notify the pipeline whether each element of the array in the buffer represents per-vertex or per-instance data
-            attributes: &Self::ATTRIBUTES,
-            // attributes: &[
-            //     // describe individual parts of the vertex - generally a 1:1 mapping with a struct's field, which is our case
-            //     wgpu::VertexAttribute {
-            //         offset: 0,          // defines the offset in bytes until the attribute starts
-            //         shader_location: 0, // tells the shader where to store this attribute at i.e. `@location(0)` x: vec3<f32>
-            //         format: wgpu::VertexFormat::Float32x3, // tells the shader the shape of the attribute
-            //     },
-            //     wgpu::VertexAttribute {
-            //         offset: std::mem::size_of::<[f32; 3]>() as wgpu::BufferAddress,
-            //         shader_location: 1,
-            //         format: wgpu::VertexFormat::Float32x3,
-            //     },
-            // ],
-        }
-    }
+    // `@location(0)` is the position and `@location(1)` the color in the shader
+    const ATTRIBUTES: &'static [wgpu::VertexAttribute] = vertex_attributes!(PolygonVertex {
+        position => 0: Float32x3,
+        color => 1: Float32x3,
+    });
+}
 
+impl PolygonGeometry for PolygonVertex {
     // lags like crazy on my computer - look into why?
-    fn gen_polygon(n: u16, radius: f32, aspect: f32) -> (Vec<PolygonVertex>, Vec<u16>) {
+    // positions are in world units, the projection uniform takes care of the aspect ratio
+    fn gen_polygon(n: u16, radius: f32, color: [f32; 3]) -> (Vec<PolygonVertex>, Vec<u16>) {
         use std::f32::consts::PI;
 
+        // counted in usize, since (n - 2) * 3 overflows a u16 well before n does
         let mut vertices = Vec::with_capacity(n as usize);
-        let mut indices = Vec::with_capacity(((n - 2) * 3) as usize);
+        let mut indices = Vec::with_capacity((n as usize).saturating_sub(2) * 3);
 
         for i in 0..n {
             let angle = 2.0 * PI * (i as f32) / (n as f32);
-            let x = radius * angle.cos() / aspect;
+            let x = radius * angle.cos();
             let y = radius * angle.sin();
             vertices.push(PolygonVertex {
                 position: [x, y, 0.0],
-                color: [1.0, 0.0, 0.0], // Example color
+                color,
             });
         }
 
-        for i in 1..(n - 1) {
-            indices.extend_from_slice(&[0, i as u16, (i + 1) as u16]);
+        for i in 1..n.saturating_sub(1) {
+            indices.extend_from_slice(&[0, i, i + 1]);
         }
 
         (vertices, indices)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::PolygonVertex;
+    use crate::utils::types::buffers::PolygonGeometry;
+
+    #[wasm_bindgen_test]
+    fn fans_out_from_the_first_vertex() {
+        let (vertices, indices) = PolygonVertex::gen_polygon(5, 0.5, [1.0, 0.0, 0.0]);
+        assert_eq!(vertices.len(), 5);
+        assert_eq!(indices, [0, 1, 2, 0, 2, 3, 0, 3, 4]);
+    }
+
+    #[wasm_bindgen_test]
+    fn the_most_sides_a_u16_index_can_address() {
+        let (vertices, indices) = PolygonVertex::gen_polygon(u16::MAX, 0.5, [1.0, 0.0, 0.0]);
+        assert_eq!(vertices.len(), u16::MAX as usize);
+        assert_eq!(indices.len(), (u16::MAX as usize - 2) * 3);
+        assert_eq!(indices.iter().max(), Some(&(u16::MAX - 1)));
+    }
+
+    #[wasm_bindgen_test]
+    fn fewer_than_three_sides_have_no_triangles() {
+        for sides in 0..3 {
+            let (vertices, indices) = PolygonVertex::gen_polygon(sides, 0.5, [1.0, 0.0, 0.0]);
+            assert_eq!(vertices.len(), sides as usize);
+            assert!(indices.is_empty());
+        }
+    }
+}
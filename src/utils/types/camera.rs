@@ -0,0 +1,170 @@
+use cgmath::{Deg, Matrix4, Point3, Vector3};
+
+/// cgmath builds OpenGL style matrices with a clip space depth of -1..1, wgpu expects 0..1.
+#[rustfmt::skip]
+pub const OPENGL_TO_WGPU_MATRIX: Matrix4<f32> = Matrix4::new(
+    1.0, 0.0, 0.0, 0.0,
+    0.0, 1.0, 0.0, 0.0,
+    0.0, 0.0, 0.5, 0.0,
+    0.0, 0.0, 0.5, 1.0,
+);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CameraMode {
+    /// Parallel projection, sized by [`Camera::ortho_size`]. Suits 2D scenes and diagrams.
+    #[default]
+    Orthographic,
+    /// Perspective projection with a vertical field of view of [`Camera::fovy`].
+    Perspective,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    pub eye: Point3<f32>,
+    pub target: Point3<f32>,
+    pub up: Vector3<f32>,
+    /// Width over height of the surface, kept up to date on resize.
+    pub aspect: f32,
+    pub fovy: Deg<f32>,
+    pub znear: f32,
+    pub zfar: f32,
+    /// Half the extent of the shorter surface side in world units, for orthographic mode.
+    pub ortho_size: f32,
+    pub mode: CameraMode,
+}
+
+impl Default for Camera {
+    /// Looks down -z at the origin, with the shorter side of the canvas spanning -1..1.
+    fn default() -> Self {
+        Self {
+            eye: Point3::new(0.0, 0.0, 1.0),
+            target: Point3::new(0.0, 0.0, 0.0),
+            up: Vector3::unit_y(),
+            aspect: 1.0,
+            fovy: Deg(45.0),
+            znear: 0.1,
+            zfar: 100.0,
+            ortho_size: 1.0,
+            mode: CameraMode::Orthographic,
+        }
+    }
+}
+
+impl Camera {
+    /// Left, right, bottom and top of the orthographic view volume. The shorter side spans
+    /// `-ortho_size..ortho_size` so geometry keeps its proportions at any aspect ratio.
+    pub fn ortho_bounds(&self) -> (f32, f32, f32, f32) {
+        let (half_width, half_height) = if self.aspect >= 1.0 {
+            (self.ortho_size * self.aspect, self.ortho_size)
+        } else {
+            (self.ortho_size, self.ortho_size / self.aspect)
+        };
+
+        (-half_width, half_width, -half_height, half_height)
+    }
+
+    pub fn build_view_matrix(&self) -> Matrix4<f32> {
+        Matrix4::look_at_rh(self.eye, self.target, self.up)
+    }
+
+    pub fn build_projection_matrix(&self) -> Matrix4<f32> {
+        match self.mode {
+            CameraMode::Orthographic => {
+                let (left, right, bottom, top) = self.ortho_bounds();
+                cgmath::ortho(left, right, bottom, top, self.znear, self.zfar)
+            }
+            CameraMode::Perspective => cgmath::perspective(self.fovy, self.aspect, self.znear, self.zfar),
+        }
+    }
+
+    pub fn build_view_projection_matrix(&self) -> Matrix4<f32> {
+        OPENGL_TO_WGPU_MATRIX * self.build_projection_matrix() * self.build_view_matrix()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Deg, EuclideanSpace, Point3, Vector3};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::{Camera, CameraMode};
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-5, "expected {expected}, got {actual}");
+    }
+
+    /// Where `point` ends up in normalized device coordinates.
+    fn project(camera: &Camera, point: Point3<f32>) -> Vector3<f32> {
+        let clip = camera.build_view_projection_matrix() * point.to_homogeneous();
+        clip.truncate() / clip.w
+    }
+
+    #[wasm_bindgen_test]
+    fn ortho_bounds_widen_the_longer_side() {
+        let wide = Camera {
+            aspect: 2.0,
+            ortho_size: 1.5,
+            ..Camera::default()
+        };
+        assert_eq!(wide.ortho_bounds(), (-3.0, 3.0, -1.5, 1.5));
+
+        let tall = Camera {
+            aspect: 0.5,
+            ortho_size: 1.5,
+            ..Camera::default()
+        };
+        assert_eq!(tall.ortho_bounds(), (-1.5, 1.5, -3.0, 3.0));
+    }
+
+    #[wasm_bindgen_test]
+    fn orthographic_origin_lands_in_wgpu_depth_range() {
+        let ndc = project(&Camera::default(), Point3::origin());
+        assert_close(ndc.x, 0.0);
+        assert_close(ndc.y, 0.0);
+        assert!((0.0..=1.0).contains(&ndc.z), "depth {} is outside 0..1", ndc.z);
+    }
+
+    #[wasm_bindgen_test]
+    fn orthographic_ortho_size_spans_the_shorter_axis() {
+        let wide = Camera {
+            aspect: 2.0,
+            ortho_size: 1.5,
+            ..Camera::default()
+        };
+        assert_close(project(&wide, Point3::new(0.0, 1.5, 0.0)).y, 1.0);
+        assert_close(project(&wide, Point3::new(0.0, -1.5, 0.0)).y, -1.0);
+        assert_close(project(&wide, Point3::new(3.0, 0.0, 0.0)).x, 1.0);
+
+        let tall = Camera {
+            aspect: 0.5,
+            ortho_size: 1.5,
+            ..Camera::default()
+        };
+        assert_close(project(&tall, Point3::new(1.5, 0.0, 0.0)).x, 1.0);
+        assert_close(project(&tall, Point3::new(-1.5, 0.0, 0.0)).x, -1.0);
+        assert_close(project(&tall, Point3::new(0.0, 3.0, 0.0)).y, 1.0);
+    }
+
+    #[wasm_bindgen_test]
+    fn perspective_follows_the_field_of_view_and_depth_range() {
+        let camera = Camera {
+            mode: CameraMode::Perspective,
+            fovy: Deg(90.0),
+            aspect: 2.0,
+            ..Camera::default()
+        };
+
+        // a 90 degree field of view reaches as far up as the point is away from the eye
+        let edge = project(&camera, Point3::new(0.0, 1.0, 0.0));
+        assert_close(edge.y, 1.0);
+        assert_close(project(&camera, Point3::new(2.0, 0.0, 0.0)).x, 1.0);
+
+        // twice as far away, the same offset covers half the screen
+        assert_close(project(&camera, Point3::new(0.0, 1.0, -1.0)).y, 0.5);
+
+        let eye_z = camera.eye.z;
+        assert_close(project(&camera, Point3::new(0.0, 0.0, eye_z - camera.znear)).z, 0.0);
+        assert_close(project(&camera, Point3::new(0.0, 0.0, eye_z - camera.zfar)).z, 1.0);
+        assert!((0.0..=1.0).contains(&edge.z));
+    }
+}
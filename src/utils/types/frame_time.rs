@@ -0,0 +1,209 @@
+/// Timing of one frame, as handed to [`Renderer::update`](crate::Renderer::update) and
+/// [`Renderer::fixed_update`](crate::Renderer::fixed_update).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FrameTime {
+    /// Seconds since the previous frame, scaled by the time scale and zero while paused.
+    pub delta: f32,
+    /// Scaled seconds since the first frame, not counting pauses.
+    pub elapsed: f64,
+    /// Number of the frame (or fixed step), starting at `0`.
+    pub frame: u64,
+}
+
+/// Turns `requestAnimationFrame` timestamps into [`FrameTime`]s and, when a fixed timestep
+/// is set, into a whole number of simulation steps per frame.
+#[derive(Debug, Clone)]
+pub(crate) struct FrameClock {
+    last_timestamp: Option<f64>,
+    elapsed: f64,
+    frame: u64,
+    accumulator: f64,
+    fixed_elapsed: f64,
+    fixed_step: u64,
+}
+
+impl FrameClock {
+    /// Longest delta a single frame may report, so a stall (a background tab, a breakpoint)
+    /// does not fling the scene forward.
+    const MAX_DELTA: f64 = 0.1;
+    /// Most fixed steps run in one frame; past this the simulation slows down instead of
+    /// spiralling into ever longer frames.
+    const MAX_FIXED_STEPS: u32 = 8;
+
+    pub fn new() -> Self {
+        Self {
+            last_timestamp: None,
+            elapsed: 0.0,
+            frame: 0,
+            accumulator: 0.0,
+            fixed_elapsed: 0.0,
+            fixed_step: 0,
+        }
+    }
+
+    /// Advances to the frame at `timestamp` (milliseconds, as passed to the rAF callback).
+    pub fn tick(&mut self, timestamp: f64, paused: bool, time_scale: f64) -> FrameTime {
+        let raw = self
+            .last_timestamp
+            .map_or(0.0, |last| ((timestamp - last) / 1000.0).clamp(0.0, Self::MAX_DELTA));
+        self.last_timestamp = Some(timestamp);
+
+        let delta = if paused { 0.0 } else { raw * time_scale.max(0.0) };
+        self.elapsed += delta;
+        self.accumulator += delta;
+
+        let time = FrameTime {
+            delta: delta as f32,
+            elapsed: self.elapsed,
+            frame: self.frame,
+        };
+        self.frame += 1;
+
+        time
+    }
+
+    /// The fixed steps of `step` seconds that fit into the time accumulated so far.
+    pub fn fixed_steps(&mut self, step: f64) -> Vec<FrameTime> {
+        if step <= 0.0 {
+            return Vec::new();
+        }
+
+        let mut steps = Vec::new();
+        while self.accumulator >= step && steps.len() < Self::MAX_FIXED_STEPS as usize {
+            self.accumulator -= step;
+            self.fixed_elapsed += step;
+            steps.push(FrameTime {
+                delta: step as f32,
+                elapsed: self.fixed_elapsed,
+                frame: self.fixed_step,
+            });
+            self.fixed_step += 1;
+        }
+
+        // whatever is left over past the cap is dropped rather than carried into the next frame
+        if steps.len() == Self::MAX_FIXED_STEPS as usize {
+            self.accumulator = 0.0;
+        }
+
+        steps
+    }
+
+    /// Forgets the previous timestamp, so the first frame after a suspension has a zero delta
+    /// instead of covering the whole time the loop was stopped.
+    pub fn reset(&mut self) {
+        self.last_timestamp = None;
+        self.accumulator = 0.0;
+    }
+
+    /// Drops the accumulated time without running it, e.g. when no fixed timestep is used.
+    pub fn clear_accumulator(&mut self) {
+        self.accumulator = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::FrameClock;
+
+    // 1/64 and 1/128 of a second, exact in binary so the step counts don't depend on rounding
+    const FRAME_MS: f64 = 15.625;
+    const FRAME: f64 = FRAME_MS / 1000.0;
+    const HALF_FRAME: f64 = FRAME / 2.0;
+
+    #[wasm_bindgen_test]
+    fn first_frame_has_no_delta() {
+        let mut clock = FrameClock::new();
+        let time = clock.tick(1234.0, false, 1.0);
+        assert_eq!(time.delta, 0.0);
+        assert_eq!(time.frame, 0);
+
+        let time = clock.tick(1234.0 + FRAME_MS, false, 1.0);
+        assert_eq!(time.delta, FRAME as f32);
+        assert_eq!(time.elapsed, FRAME);
+        assert_eq!(time.frame, 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn runs_as_many_fixed_steps_as_fit() {
+        let mut clock = FrameClock::new();
+        clock.tick(0.0, false, 1.0);
+        assert!(clock.fixed_steps(HALF_FRAME).is_empty());
+
+        clock.tick(FRAME_MS, false, 1.0);
+        let steps = clock.fixed_steps(HALF_FRAME);
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[1].frame, 1);
+        assert_eq!(steps[1].elapsed, FRAME);
+        assert!(steps.iter().all(|step| step.delta == HALF_FRAME as f32));
+
+        // a step longer than the frame carries the remainder over
+        clock.tick(2.0 * FRAME_MS, false, 1.0);
+        assert!(clock.fixed_steps(2.0 * FRAME).is_empty());
+        clock.tick(3.0 * FRAME_MS, false, 1.0);
+        assert_eq!(clock.fixed_steps(2.0 * FRAME).len(), 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn caps_fixed_steps_and_drops_the_rest() {
+        let mut clock = FrameClock::new();
+        clock.tick(0.0, false, 1.0);
+        // a stall, clamped to MAX_DELTA, which still holds more than MAX_FIXED_STEPS steps
+        let time = clock.tick(10_000.0, false, 1.0);
+        assert_eq!(time.delta, FrameClock::MAX_DELTA as f32);
+
+        let step = FrameClock::MAX_DELTA / 20.0;
+        assert_eq!(clock.fixed_steps(step).len(), FrameClock::MAX_FIXED_STEPS as usize);
+        assert!(clock.fixed_steps(step).is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn pausing_stops_time_and_fixed_steps() {
+        let mut clock = FrameClock::new();
+        clock.tick(0.0, false, 1.0);
+        clock.tick(FRAME_MS, false, 1.0);
+        clock.fixed_steps(FRAME);
+
+        let time = clock.tick(2.0 * FRAME_MS, true, 1.0);
+        assert_eq!(time.delta, 0.0);
+        assert_eq!(time.elapsed, FRAME);
+        assert_eq!(time.frame, 2);
+        assert!(clock.fixed_steps(HALF_FRAME).is_empty());
+
+        // resuming doesn't make up for the paused frame
+        let time = clock.tick(3.0 * FRAME_MS, false, 1.0);
+        assert_eq!(time.elapsed, 2.0 * FRAME);
+    }
+
+    #[wasm_bindgen_test]
+    fn time_scale_stretches_delta_and_fixed_steps() {
+        let mut clock = FrameClock::new();
+        clock.tick(0.0, false, 0.5);
+        let time = clock.tick(FRAME_MS, false, 0.5);
+        assert_eq!(time.delta, HALF_FRAME as f32);
+        assert_eq!(clock.fixed_steps(HALF_FRAME).len(), 1);
+
+        let time = clock.tick(2.0 * FRAME_MS, false, 2.0);
+        assert_eq!(time.delta, 2.0 * FRAME as f32);
+        assert_eq!(clock.fixed_steps(HALF_FRAME).len(), 4);
+
+        // a negative scale doesn't run time backwards
+        let time = clock.tick(3.0 * FRAME_MS, false, -1.0);
+        assert_eq!(time.delta, 0.0);
+        assert_eq!(time.elapsed, 2.5 * FRAME);
+    }
+
+    #[wasm_bindgen_test]
+    fn reset_starts_timing_over() {
+        let mut clock = FrameClock::new();
+        clock.tick(0.0, false, 1.0);
+        clock.tick(FRAME_MS, false, 1.0);
+
+        clock.reset();
+        let time = clock.tick(60_000.0, false, 1.0);
+        assert_eq!(time.delta, 0.0);
+        assert_eq!(time.elapsed, FRAME);
+        assert!(clock.fixed_steps(HALF_FRAME).is_empty());
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+
+/// A button of the W3C "standard" gamepad layout, named after its position so it means the
+/// same on Xbox, PlayStation and Switch style controllers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    /// A on Xbox, Cross on PlayStation.
+    South,
+    /// B on Xbox, Circle on PlayStation.
+    East,
+    /// X on Xbox, Square on PlayStation.
+    West,
+    /// Y on Xbox, Triangle on PlayStation.
+    North,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    LeftStick,
+    RightStick,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    Home,
+    /// Anything past the standard layout, by its index in `Gamepad.buttons`.
+    Other(usize),
+}
+
+impl GamepadButton {
+    pub fn from_index(index: usize) -> Self {
+        match index {
+            0 => GamepadButton::South,
+            1 => GamepadButton::East,
+            2 => GamepadButton::West,
+            3 => GamepadButton::North,
+            4 => GamepadButton::LeftBumper,
+            5 => GamepadButton::RightBumper,
+            6 => GamepadButton::LeftTrigger,
+            7 => GamepadButton::RightTrigger,
+            8 => GamepadButton::Select,
+            9 => GamepadButton::Start,
+            10 => GamepadButton::LeftStick,
+            11 => GamepadButton::RightStick,
+            12 => GamepadButton::DPadUp,
+            13 => GamepadButton::DPadDown,
+            14 => GamepadButton::DPadLeft,
+            15 => GamepadButton::DPadRight,
+            16 => GamepadButton::Home,
+            other => GamepadButton::Other(other),
+        }
+    }
+}
+
+/// An axis of the standard gamepad layout. Stick Y axes point up, unlike the raw
+/// `Gamepad.axes`, and the analog triggers are reported as axes from `0.0` to `1.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+/// One button as read from `Gamepad.buttons`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct GamepadButtonState {
+    pub pressed: bool,
+    /// How far an analog button is pushed, `0.0..=1.0`; digital buttons are `0.0` or `1.0`.
+    pub value: f32,
+}
+
+/// A gamepad as read by `navigator.getGamepads()` during one frame.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GamepadSnapshot {
+    /// `Gamepad.index`, stable for as long as the pad stays connected.
+    pub index: u32,
+    pub id: String,
+    /// Whether the browser mapped it to the standard layout; if not, buttons and axes are
+    /// only guessed from their indices.
+    pub standard: bool,
+    pub buttons: Vec<GamepadButtonState>,
+    /// Raw `Gamepad.axes`, without any deadzone applied.
+    pub axes: Vec<f32>,
+}
+
+/// A `gamepadconnected` or `gamepaddisconnected`, as handed to a [`Renderer`](crate::Renderer).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GamepadConnection {
+    pub index: u32,
+    pub id: String,
+    pub connected: bool,
+}
+
+/// One connected gamepad as seen by one frame, with the deadzone already applied.
+#[derive(Debug, Clone, Default)]
+pub struct GamepadState {
+    id: String,
+    pressed: HashSet<GamepadButton>,
+    just_pressed: HashSet<GamepadButton>,
+    just_released: HashSet<GamepadButton>,
+    left_stick: [f32; 2],
+    right_stick: [f32; 2],
+    triggers: [f32; 2],
+}
+
+impl GamepadState {
+    /// Takes over this frame's `snapshot`, comparing its buttons with the previous frame.
+    pub fn update(&mut self, snapshot: &GamepadSnapshot, deadzone: f32) {
+        self.id.clone_from(&snapshot.id);
+        self.just_pressed.clear();
+        self.just_released.clear();
+
+        for (index, state) in snapshot.buttons.iter().enumerate() {
+            let button = GamepadButton::from_index(index);
+            if state.pressed {
+                if self.pressed.insert(button) {
+                    self.just_pressed.insert(button);
+                }
+            } else if self.pressed.remove(&button) {
+                self.just_released.insert(button);
+            }
+        }
+
+        let axis = |index: usize| snapshot.axes.get(index).copied().unwrap_or(0.0);
+        self.left_stick = apply_deadzone([axis(0), -axis(1)], deadzone);
+        self.right_stick = apply_deadzone([axis(2), -axis(3)], deadzone);
+
+        let trigger = |index: usize| snapshot.buttons.get(index).map_or(0.0, |button| button.value);
+        self.triggers = [trigger(6), trigger(7)];
+    }
+
+    /// Releases every held button, e.g. when the pad was disconnected mid-press.
+    pub fn release_all(&mut self) {
+        self.just_pressed.clear();
+        self.just_released = std::mem::take(&mut self.pressed);
+        self.left_stick = [0.0, 0.0];
+        self.right_stick = [0.0, 0.0];
+        self.triggers = [0.0, 0.0];
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn is_pressed(&self, button: GamepadButton) -> bool {
+        self.pressed.contains(&button)
+    }
+
+    pub fn is_just_pressed(&self, button: GamepadButton) -> bool {
+        self.just_pressed.contains(&button)
+    }
+
+    pub fn is_just_released(&self, button: GamepadButton) -> bool {
+        self.just_released.contains(&button)
+    }
+
+    /// Whether a button is held or a stick is pushed past the deadzone.
+    pub fn is_active(&self) -> bool {
+        !self.pressed.is_empty() || self.left_stick != [0.0, 0.0] || self.right_stick != [0.0, 0.0]
+    }
+
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        match axis {
+            GamepadAxis::LeftStickX => self.left_stick[0],
+            GamepadAxis::LeftStickY => self.left_stick[1],
+            GamepadAxis::RightStickX => self.right_stick[0],
+            GamepadAxis::RightStickY => self.right_stick[1],
+            GamepadAxis::LeftTrigger => self.triggers[0],
+            GamepadAxis::RightTrigger => self.triggers[1],
+        }
+    }
+}
+
+/// A radial deadzone: the stick reads zero until it leaves a circle of radius `deadzone`,
+/// and is rescaled past it so the output still ramps smoothly from `0.0` to `1.0`.
+fn apply_deadzone(stick: [f32; 2], deadzone: f32) -> [f32; 2] {
+    let magnitude = stick[0].hypot(stick[1]);
+    if magnitude <= deadzone || magnitude <= f32::EPSILON {
+        return [0.0, 0.0];
+    }
+
+    let scaled = ((magnitude - deadzone) / (1.0 - deadzone)).min(1.0);
+    [stick[0] / magnitude * scaled, stick[1] / magnitude * scaled]
+}
@@ -0,0 +1,456 @@
+use crate::utils::types::pointer_event::{PointerEvent, PointerPosition};
+
+/// A gesture recognized from one or two touch contacts on the canvas.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    /// A single short touch that barely moved.
+    Tap { position: PointerPosition },
+    /// A second tap close to the first one, reported instead of its [`Gesture::Tap`].
+    DoubleTap { position: PointerPosition },
+    /// One finger, or the midpoint of two, moved by this much since the last report.
+    Drag {
+        fingers: usize,
+        delta: [f64; 2],
+        ndc_delta: [f32; 2],
+    },
+    /// Two fingers moved apart (`scale > 1.0`) or together (`scale < 1.0`).
+    Pinch { scale: f32, center: PointerPosition },
+    /// Two fingers turned around their midpoint, counter-clockwise on screen for positive angles.
+    Rotate { angle: f32, center: PointerPosition },
+}
+
+/// Thresholds used by a [`GestureRecognizer`]. Distances are in canvas pixels and times in
+/// milliseconds, matching `PointerEvent.timeStamp`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GestureConfig {
+    /// How far a contact may wander and still count as a tap rather than a drag.
+    pub tap_slop: f64,
+    /// How long a contact may stay down and still count as a tap.
+    pub tap_max_duration: f64,
+    /// How soon after a tap the next one has to land to make a double tap.
+    pub double_tap_interval: f64,
+    /// How close to the first tap the second one has to land to make a double tap.
+    pub double_tap_slop: f64,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            tap_slop: 10.0,
+            tap_max_duration: 300.0,
+            double_tap_interval: 300.0,
+            double_tap_slop: 30.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TrackedPointer {
+    id: i32,
+    start: PointerPosition,
+    start_time: f64,
+    // where the last drag was reported from
+    anchor: PointerPosition,
+    position: PointerPosition,
+}
+
+/// Turns the pointer events of touch contacts into [`Gesture`]s.
+///
+/// Only the first two contacts are followed; a third is ignored until one of them lifts.
+/// Being plain data, it can be driven by synthetic [`PointerEvent`]s as well as real ones.
+#[derive(Debug, Clone, Default)]
+pub struct GestureRecognizer {
+    pub config: GestureConfig,
+    pointers: Vec<TrackedPointer>,
+    // cleared as soon as the touch turns into something other than a tap
+    tap_possible: bool,
+    dragging: bool,
+    // position and time of the last tap, for spotting a double tap
+    last_tap: Option<(PointerPosition, f64)>,
+}
+
+impl GestureRecognizer {
+    pub fn new(config: GestureConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// How many contacts are currently followed.
+    pub fn active_pointers(&self) -> usize {
+        self.pointers.len()
+    }
+
+    pub fn pointer_down(&mut self, event: &PointerEvent) -> Vec<Gesture> {
+        if self.pointers.len() >= 2 || self.pointers.iter().any(|pointer| pointer.id == event.pointer_id) {
+            return Vec::new();
+        }
+
+        self.tap_possible = self.pointers.is_empty();
+        self.dragging = false;
+        // the first finger's drag starts over from the midpoint once a second one lands
+        for pointer in &mut self.pointers {
+            pointer.anchor = pointer.position;
+        }
+
+        self.pointers.push(TrackedPointer {
+            id: event.pointer_id,
+            start: event.position,
+            start_time: event.time_stamp,
+            anchor: event.position,
+            position: event.position,
+        });
+
+        Vec::new()
+    }
+
+    pub fn pointer_move(&mut self, event: &PointerEvent) -> Vec<Gesture> {
+        let Some(index) = self.pointers.iter().position(|pointer| pointer.id == event.pointer_id) else {
+            return Vec::new();
+        };
+
+        match self.pointers.len() {
+            1 => self.move_one(event.position),
+            _ => self.move_two(index, event.position),
+        }
+    }
+
+    pub fn pointer_up(&mut self, event: &PointerEvent) -> Vec<Gesture> {
+        let Some(pointer) = self.lift(event.pointer_id) else {
+            return Vec::new();
+        };
+
+        let mut gestures = Vec::new();
+
+        if self.pointers.is_empty() {
+            let moved = distance(pointer.start, event.position) > self.config.tap_slop;
+            let quick = event.time_stamp - pointer.start_time <= self.config.tap_max_duration;
+
+            if self.tap_possible && !moved && quick {
+                gestures.push(self.tap(event.position, event.time_stamp));
+            }
+        }
+
+        gestures
+    }
+
+    /// Drops a contact the browser took over, e.g. for a system gesture. Unlike
+    /// [`GestureRecognizer::pointer_up`] this never completes a tap.
+    pub fn pointer_cancel(&mut self, event: &PointerEvent) {
+        if self.lift(event.pointer_id).is_some() && self.pointers.is_empty() {
+            self.tap_possible = false;
+            self.dragging = false;
+        }
+    }
+
+    /// Stops following contact `id`, returning it if it was followed at all.
+    fn lift(&mut self, id: i32) -> Option<TrackedPointer> {
+        let index = self.pointers.iter().position(|pointer| pointer.id == id)?;
+        let pointer = self.pointers.remove(index);
+
+        if !self.pointers.is_empty() {
+            // the remaining finger carries on as a one finger drag without jumping
+            self.tap_possible = false;
+            self.dragging = true;
+            for pointer in &mut self.pointers {
+                pointer.anchor = pointer.position;
+            }
+        }
+
+        Some(pointer)
+    }
+
+    /// Forgets every contact, e.g. when the page loses focus mid-gesture.
+    pub fn reset(&mut self) {
+        self.pointers.clear();
+        self.tap_possible = false;
+        self.dragging = false;
+    }
+
+    fn tap(&mut self, position: PointerPosition, time: f64) -> Gesture {
+        let double = self.last_tap.is_some_and(|(last, last_time)| {
+            time - last_time <= self.config.double_tap_interval
+                && distance(last, position) <= self.config.double_tap_slop
+        });
+
+        if double {
+            // a third tap starts a new pair instead of making another double tap
+            self.last_tap = None;
+            Gesture::DoubleTap { position }
+        } else {
+            self.last_tap = Some((position, time));
+            Gesture::Tap { position }
+        }
+    }
+
+    fn move_one(&mut self, position: PointerPosition) -> Vec<Gesture> {
+        let pointer = &mut self.pointers[0];
+        pointer.position = position;
+
+        if !self.dragging && distance(pointer.start, position) <= self.config.tap_slop {
+            return Vec::new();
+        }
+
+        self.dragging = true;
+        self.tap_possible = false;
+
+        let gesture = drag(1, pointer.anchor, position);
+        pointer.anchor = position;
+
+        vec![gesture]
+    }
+
+    fn move_two(&mut self, index: usize, position: PointerPosition) -> Vec<Gesture> {
+        self.tap_possible = false;
+        self.dragging = true;
+
+        let (a, b) = (self.pointers[0], self.pointers[1]);
+        self.pointers[index].position = position;
+        let (new_a, new_b) = (self.pointers[0].position, self.pointers[1].position);
+
+        let old_center = midpoint(a.anchor, b.anchor);
+        let new_center = midpoint(new_a, new_b);
+        let old_distance = distance(a.anchor, b.anchor);
+        let new_distance = distance(new_a, new_b);
+
+        let mut gestures = vec![drag(2, old_center, new_center)];
+
+        if old_distance > f64::EPSILON && new_distance > f64::EPSILON {
+            gestures.push(Gesture::Pinch {
+                scale: (new_distance / old_distance) as f32,
+                center: new_center,
+            });
+
+            // screen y points down, so the angles are negated to turn counter-clockwise positive
+            let old_angle = -(b.anchor.physical.y - a.anchor.physical.y).atan2(b.anchor.physical.x - a.anchor.physical.x);
+            let new_angle = -(new_b.physical.y - new_a.physical.y).atan2(new_b.physical.x - new_a.physical.x);
+            let mut angle = new_angle - old_angle;
+            if angle > std::f64::consts::PI {
+                angle -= std::f64::consts::TAU;
+            } else if angle < -std::f64::consts::PI {
+                angle += std::f64::consts::TAU;
+            }
+            gestures.push(Gesture::Rotate {
+                angle: angle as f32,
+                center: new_center,
+            });
+        }
+
+        for pointer in &mut self.pointers {
+            pointer.anchor = pointer.position;
+        }
+
+        gestures
+    }
+}
+
+fn distance(a: PointerPosition, b: PointerPosition) -> f64 {
+    (b.physical.x - a.physical.x).hypot(b.physical.y - a.physical.y)
+}
+
+fn midpoint(a: PointerPosition, b: PointerPosition) -> PointerPosition {
+    let mut center = a;
+    center.physical.x = (a.physical.x + b.physical.x) / 2.0;
+    center.physical.y = (a.physical.y + b.physical.y) / 2.0;
+    center.ndc = [(a.ndc[0] + b.ndc[0]) / 2.0, (a.ndc[1] + b.ndc[1]) / 2.0];
+    center
+}
+
+fn drag(fingers: usize, from: PointerPosition, to: PointerPosition) -> Gesture {
+    Gesture::Drag {
+        fingers,
+        delta: [to.physical.x - from.physical.x, to.physical.y - from.physical.y],
+        ndc_delta: [to.ndc[0] - from.ndc[0], to.ndc[1] - from.ndc[1]],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::{Gesture, GestureRecognizer};
+    use crate::utils::helpers::testing::touch;
+
+    fn tap_at(recognizer: &mut GestureRecognizer, x: f64, time: f64) -> Vec<Gesture> {
+        recognizer.pointer_down(&touch(1, x, 100.0, time));
+        recognizer.pointer_up(&touch(1, x, 100.0, time + 50.0))
+    }
+
+    fn single(gestures: Vec<Gesture>) -> Gesture {
+        assert_eq!(gestures.len(), 1, "expected one gesture, got {gestures:?}");
+        gestures[0]
+    }
+
+    /// The `Pinch` and `Rotate` reported by a two finger move, ignoring the `Drag`.
+    fn pinch_and_rotate(gestures: &[Gesture]) -> (f32, f32) {
+        let scale = gestures.iter().find_map(|gesture| match gesture {
+            Gesture::Pinch { scale, .. } => Some(*scale),
+            _ => None,
+        });
+        let angle = gestures.iter().find_map(|gesture| match gesture {
+            Gesture::Rotate { angle, .. } => Some(*angle),
+            _ => None,
+        });
+        (scale.expect("no pinch"), angle.expect("no rotation"))
+    }
+
+    #[wasm_bindgen_test]
+    fn short_touch_is_a_tap() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.pointer_down(&touch(1, 100.0, 100.0, 0.0));
+        // a little wobble stays within the slop
+        assert!(recognizer.pointer_move(&touch(1, 104.0, 100.0, 50.0)).is_empty());
+
+        let gesture = single(recognizer.pointer_up(&touch(1, 104.0, 100.0, 100.0)));
+        assert!(matches!(gesture, Gesture::Tap { position } if position.physical.x == 104.0));
+        assert_eq!(recognizer.active_pointers(), 0);
+    }
+
+    #[wasm_bindgen_test]
+    fn moving_past_the_slop_is_not_a_tap() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.pointer_down(&touch(1, 100.0, 100.0, 0.0));
+        recognizer.pointer_move(&touch(1, 120.0, 100.0, 50.0));
+        recognizer.pointer_move(&touch(1, 100.0, 100.0, 80.0));
+
+        // coming back doesn't make it a tap again
+        assert!(recognizer.pointer_up(&touch(1, 100.0, 100.0, 100.0)).is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn holding_too_long_is_not_a_tap() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.pointer_down(&touch(1, 100.0, 100.0, 0.0));
+
+        let limit = recognizer.config.tap_max_duration;
+        assert!(recognizer.pointer_up(&touch(1, 100.0, 100.0, limit + 1.0)).is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn second_tap_nearby_and_soon_is_a_double_tap() {
+        let mut recognizer = GestureRecognizer::default();
+        assert!(matches!(single(tap_at(&mut recognizer, 100.0, 0.0)), Gesture::Tap { .. }));
+        assert!(matches!(single(tap_at(&mut recognizer, 110.0, 200.0)), Gesture::DoubleTap { .. }));
+        // a third tap starts a new pair
+        assert!(matches!(single(tap_at(&mut recognizer, 110.0, 400.0)), Gesture::Tap { .. }));
+    }
+
+    #[wasm_bindgen_test]
+    fn second_tap_too_late_or_too_far_is_a_tap() {
+        let mut recognizer = GestureRecognizer::default();
+        tap_at(&mut recognizer, 100.0, 0.0);
+        let late = recognizer.config.double_tap_interval + 100.0;
+        assert!(matches!(single(tap_at(&mut recognizer, 100.0, late)), Gesture::Tap { .. }));
+
+        let far = 100.0 + recognizer.config.double_tap_slop + 1.0;
+        assert!(matches!(single(tap_at(&mut recognizer, far, late + 100.0)), Gesture::Tap { .. }));
+    }
+
+    #[wasm_bindgen_test]
+    fn one_finger_drag_reports_each_step() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.pointer_down(&touch(1, 100.0, 100.0, 0.0));
+        assert!(recognizer.pointer_move(&touch(1, 105.0, 100.0, 10.0)).is_empty());
+
+        // the first step starts from where the finger went down
+        let gesture = single(recognizer.pointer_move(&touch(1, 120.0, 100.0, 20.0)));
+        assert_eq!(
+            gesture,
+            Gesture::Drag {
+                fingers: 1,
+                delta: [20.0, 0.0],
+                ndc_delta: [0.2, 0.0],
+            }
+        );
+
+        // once dragging, even small steps are reported; NDC y points up
+        let gesture = single(recognizer.pointer_move(&touch(1, 120.0, 102.0, 30.0)));
+        assert!(matches!(gesture, Gesture::Drag { fingers: 1, delta: [0.0, 2.0], ndc_delta } if ndc_delta[1] < 0.0));
+    }
+
+    #[wasm_bindgen_test]
+    fn spreading_two_fingers_pinches() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.pointer_down(&touch(1, 50.0, 100.0, 0.0));
+        recognizer.pointer_down(&touch(2, 150.0, 100.0, 0.0));
+
+        let gestures = recognizer.pointer_move(&touch(2, 250.0, 100.0, 10.0));
+        assert!(matches!(gestures[0], Gesture::Drag { fingers: 2, delta: [50.0, 0.0], .. }));
+        let (scale, angle) = pinch_and_rotate(&gestures);
+        assert!((scale - 2.0).abs() < 1e-6);
+        assert!(angle.abs() < 1e-6);
+
+        let (scale, _) = pinch_and_rotate(&recognizer.pointer_move(&touch(2, 100.0, 100.0, 20.0)));
+        assert!((scale - 0.25).abs() < 1e-6);
+    }
+
+    #[wasm_bindgen_test]
+    fn turning_counter_clockwise_is_a_positive_rotation() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.pointer_down(&touch(1, 100.0, 100.0, 0.0));
+        recognizer.pointer_down(&touch(2, 200.0, 100.0, 0.0));
+
+        // screen y points down, so moving up is counter-clockwise around the first finger
+        let (_, angle) = pinch_and_rotate(&recognizer.pointer_move(&touch(2, 100.0, 0.0, 10.0)));
+        assert!((angle - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+
+        let (_, angle) = pinch_and_rotate(&recognizer.pointer_move(&touch(2, 200.0, 100.0, 20.0)));
+        assert!((angle + std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+    }
+
+    #[wasm_bindgen_test]
+    fn rotation_wraps_across_the_half_turn() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.pointer_down(&touch(1, 200.0, 100.0, 0.0));
+        // the second finger sits almost straight left, just below the first
+        recognizer.pointer_down(&touch(2, 100.0, 101.0, 0.0));
+
+        // moving just above crosses from -180 to +180 degrees; that's a small clockwise turn
+        let (_, angle) = pinch_and_rotate(&recognizer.pointer_move(&touch(2, 100.0, 99.0, 10.0)));
+        assert!(angle < 0.0 && angle > -0.05, "got {angle}");
+    }
+
+    #[wasm_bindgen_test]
+    fn remaining_finger_carries_on_dragging_without_a_jump() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.pointer_down(&touch(1, 50.0, 100.0, 0.0));
+        recognizer.pointer_down(&touch(2, 150.0, 100.0, 0.0));
+        recognizer.pointer_move(&touch(1, 60.0, 100.0, 10.0));
+
+        assert!(recognizer.pointer_up(&touch(2, 150.0, 100.0, 20.0)).is_empty());
+        assert_eq!(recognizer.active_pointers(), 1);
+
+        // picks up from where the finger is now, not from its midpoint with the other one,
+        // and is reported straight away even though it moved less than the tap slop
+        let gesture = single(recognizer.pointer_move(&touch(1, 63.0, 100.0, 30.0)));
+        assert!(matches!(gesture, Gesture::Drag { fingers: 1, delta: [3.0, 0.0], .. }));
+
+        // lifting the last finger after a two finger gesture isn't a tap
+        assert!(recognizer.pointer_up(&touch(1, 63.0, 100.0, 40.0)).is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn ignores_a_third_finger() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.pointer_down(&touch(1, 50.0, 100.0, 0.0));
+        recognizer.pointer_down(&touch(2, 150.0, 100.0, 0.0));
+        recognizer.pointer_down(&touch(3, 100.0, 50.0, 0.0));
+
+        assert_eq!(recognizer.active_pointers(), 2);
+        assert!(recognizer.pointer_move(&touch(3, 100.0, 0.0, 10.0)).is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn cancelled_touch_is_not_a_tap() {
+        let mut recognizer = GestureRecognizer::default();
+        recognizer.pointer_down(&touch(1, 100.0, 100.0, 0.0));
+        recognizer.pointer_cancel(&touch(1, 100.0, 100.0, 50.0));
+        assert_eq!(recognizer.active_pointers(), 0);
+
+        // nor can it turn the next tap into a double tap
+        assert!(matches!(single(tap_at(&mut recognizer, 100.0, 100.0)), Gesture::Tap { .. }));
+        // and a late up for it is ignored
+        assert!(recognizer.pointer_up(&touch(1, 100.0, 100.0, 300.0)).is_empty());
+    }
+}
@@ -0,0 +1,459 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::utils::types::gamepad::{GamepadAxis, GamepadButton, GamepadConnection, GamepadSnapshot, GamepadState};
+use crate::utils::types::gesture::{Gesture, GestureRecognizer};
+use crate::utils::types::key_event::Modifiers;
+use crate::utils::types::keycode::KeyCode;
+use crate::utils::types::pointer_event::{PointerButton, PointerEvent, PointerKind, PointerPosition, WheelEvent};
+
+/// Keyboard and pointer state as seen by one frame: which physical keys and buttons are held,
+/// which went down or up since the previous frame, and where the pointer is and has moved.
+///
+/// Touch contacts don't press buttons; they are turned into [`Gesture`]s instead. Gamepads
+/// are polled once per frame and kept per `Gamepad.index`.
+#[derive(Debug, Clone)]
+pub struct InputState {
+    pressed: HashSet<KeyCode>,
+    just_pressed: HashSet<KeyCode>,
+    just_released: HashSet<KeyCode>,
+    modifiers: Modifiers,
+    pointer: Option<PointerPosition>,
+    buttons: HashSet<PointerButton>,
+    just_pressed_buttons: HashSet<PointerButton>,
+    just_released_buttons: HashSet<PointerButton>,
+    // movement accumulated since the previous frame
+    pointer_delta: [f64; 2],
+    pointer_ndc_delta: [f32; 2],
+    pointer_motion: [f64; 2],
+    pointer_locked: bool,
+    wheel_delta: [f64; 2],
+    recognizer: GestureRecognizer,
+    gestures: Vec<Gesture>,
+    gamepads: BTreeMap<u32, GamepadState>,
+    gamepad_deadzone: f32,
+}
+
+impl Default for InputState {
+    fn default() -> Self {
+        Self {
+            pressed: HashSet::new(),
+            just_pressed: HashSet::new(),
+            just_released: HashSet::new(),
+            modifiers: Modifiers::empty(),
+            pointer: None,
+            buttons: HashSet::new(),
+            just_pressed_buttons: HashSet::new(),
+            just_released_buttons: HashSet::new(),
+            pointer_delta: [0.0, 0.0],
+            pointer_ndc_delta: [0.0, 0.0],
+            pointer_motion: [0.0, 0.0],
+            pointer_locked: false,
+            wheel_delta: [0.0, 0.0],
+            recognizer: GestureRecognizer::default(),
+            gestures: Vec::new(),
+            gamepads: BTreeMap::new(),
+            // most sticks rest within 10% of the center; a little more covers worn ones
+            gamepad_deadzone: 0.15,
+        }
+    }
+}
+
+impl InputState {
+    /// Records a `keydown`. Auto-repeats of a held key don't count as a new press.
+    pub fn press(&mut self, key: KeyCode, repeat: bool) {
+        if self.pressed.insert(key) && !repeat {
+            self.just_pressed.insert(key);
+        }
+    }
+
+    /// Records a `keyup`.
+    pub fn release(&mut self, key: KeyCode) {
+        if self.pressed.remove(&key) {
+            self.just_released.insert(key);
+        }
+    }
+
+    /// Releases every held key, e.g. on `blur`, when the matching `keyup`s will never arrive.
+    pub fn release_all(&mut self) {
+        self.just_released.extend(self.pressed.drain());
+        self.just_released_buttons.extend(self.buttons.drain());
+        self.recognizer.reset();
+        self.modifiers = Modifiers::empty();
+    }
+
+    pub fn set_modifiers(&mut self, modifiers: Modifiers) {
+        self.modifiers = modifiers;
+    }
+
+    pub fn modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
+
+    /// Forgets this frame's presses and releases; call once the frame has been updated.
+    pub fn end_frame(&mut self) {
+        self.just_pressed.clear();
+        self.just_released.clear();
+        self.just_pressed_buttons.clear();
+        self.just_released_buttons.clear();
+        self.pointer_delta = [0.0, 0.0];
+        self.pointer_ndc_delta = [0.0, 0.0];
+        self.pointer_motion = [0.0, 0.0];
+        self.wheel_delta = [0.0, 0.0];
+        self.gestures.clear();
+    }
+
+    /// Records a `pointerdown`.
+    pub fn pointer_down(&mut self, event: &PointerEvent) {
+        if event.kind == PointerKind::Touch {
+            self.pointer = Some(event.position);
+            let gestures = self.recognizer.pointer_down(event);
+            self.gestures.extend(gestures);
+            return;
+        }
+
+        self.pointer_move(event);
+        if let Some(button) = event.button {
+            if self.buttons.insert(button) {
+                self.just_pressed_buttons.insert(button);
+            }
+        }
+    }
+
+    /// Records a `pointerup`.
+    pub fn pointer_up(&mut self, event: &PointerEvent) {
+        if event.kind == PointerKind::Touch {
+            self.pointer = Some(event.position);
+            let gestures = self.recognizer.pointer_up(event);
+            self.gestures.extend(gestures);
+            return;
+        }
+
+        self.pointer_move(event);
+        if let Some(button) = event.button {
+            if self.buttons.remove(&button) {
+                self.just_released_buttons.insert(button);
+            }
+        }
+    }
+
+    /// Records a `pointercancel`: the contact is dropped without completing a tap, and a
+    /// mouse button is released as if by `pointerup`.
+    pub fn pointer_cancel(&mut self, event: &PointerEvent) {
+        if event.kind == PointerKind::Touch {
+            self.recognizer.pointer_cancel(event);
+            return;
+        }
+
+        if let Some(button) = event.button {
+            if self.buttons.remove(&button) {
+                self.just_released_buttons.insert(button);
+            }
+        }
+    }
+
+    /// Records a `pointermove`, adding to this frame's [`InputState::pointer_delta`].
+    pub fn pointer_move(&mut self, event: &PointerEvent) {
+        self.modifiers = event.modifiers;
+
+        if event.kind == PointerKind::Touch {
+            self.pointer = Some(event.position);
+            let gestures = self.recognizer.pointer_move(event);
+            self.gestures.extend(gestures);
+            return;
+        }
+
+        self.pointer_motion[0] += event.movement[0];
+        self.pointer_motion[1] += event.movement[1];
+
+        let position = event.position;
+        if let Some(last) = self.pointer {
+            self.pointer_delta[0] += position.physical.x - last.physical.x;
+            self.pointer_delta[1] += position.physical.y - last.physical.y;
+            self.pointer_ndc_delta[0] += position.ndc[0] - last.ndc[0];
+            self.pointer_ndc_delta[1] += position.ndc[1] - last.ndc[1];
+        }
+        self.pointer = Some(position);
+    }
+
+    /// Records a `wheel`, adding to this frame's [`InputState::wheel_delta`].
+    pub fn wheel(&mut self, event: &WheelEvent) {
+        self.modifiers = event.modifiers;
+        self.pointer = Some(event.position);
+        self.wheel_delta[0] += event.delta_x;
+        self.wheel_delta[1] += event.delta_y;
+    }
+
+    /// Where the pointer was last seen over the canvas, if it has been at all.
+    pub fn pointer_position(&self) -> Option<PointerPosition> {
+        self.pointer
+    }
+
+    /// How far the pointer moved since the previous frame, in canvas pixels.
+    pub fn pointer_delta(&self) -> [f64; 2] {
+        self.pointer_delta
+    }
+
+    /// How far the pointer moved since the previous frame, in NDC (`y` up).
+    pub fn pointer_ndc_delta(&self) -> [f32; 2] {
+        self.pointer_ndc_delta
+    }
+
+    /// The relative mouse motion since the previous frame, in canvas pixels, from `movementX/Y`.
+    /// This is what to steer with while [`InputState::is_pointer_locked`].
+    pub fn pointer_motion(&self) -> [f64; 2] {
+        self.pointer_motion
+    }
+
+    pub fn set_pointer_locked(&mut self, locked: bool) {
+        self.pointer_locked = locked;
+    }
+
+    /// Whether the canvas holds the pointer lock, i.e. the cursor is hidden and only
+    /// [`InputState::pointer_motion`] changes.
+    pub fn is_pointer_locked(&self) -> bool {
+        self.pointer_locked
+    }
+
+    /// How far the wheel scrolled since the previous frame, in pixels.
+    pub fn wheel_delta(&self) -> [f64; 2] {
+        self.wheel_delta
+    }
+
+    /// Touch gestures recognized since the previous frame, in the order they happened.
+    pub fn gestures(&self) -> &[Gesture] {
+        &self.gestures
+    }
+
+    /// The thresholds touch gestures are recognized with.
+    pub fn gesture_recognizer_mut(&mut self) -> &mut GestureRecognizer {
+        &mut self.recognizer
+    }
+
+    /// The summed [`Gesture::Drag`] movement in NDC for drags with `fingers` contacts.
+    pub fn touch_drag(&self, fingers: usize) -> [f32; 2] {
+        self.gestures.iter().fold([0.0, 0.0], |sum, gesture| match gesture {
+            Gesture::Drag { fingers: n, ndc_delta, .. } if *n == fingers => [sum[0] + ndc_delta[0], sum[1] + ndc_delta[1]],
+            _ => sum,
+        })
+    }
+
+    /// The combined [`Gesture::Pinch`] scale since the previous frame, `1.0` without a pinch.
+    pub fn pinch_scale(&self) -> f32 {
+        self.gestures.iter().fold(1.0, |scale, gesture| match gesture {
+            Gesture::Pinch { scale: step, .. } => scale * step,
+            _ => scale,
+        })
+    }
+
+    /// The summed [`Gesture::Rotate`] angle since the previous frame, in radians.
+    pub fn touch_rotation(&self) -> f32 {
+        self.gestures.iter().fold(0.0, |angle, gesture| match gesture {
+            Gesture::Rotate { angle: step, .. } => angle + step,
+            _ => angle,
+        })
+    }
+
+    pub fn is_button_pressed(&self, button: PointerButton) -> bool {
+        self.buttons.contains(&button)
+    }
+
+    pub fn is_button_just_pressed(&self, button: PointerButton) -> bool {
+        self.just_pressed_buttons.contains(&button)
+    }
+
+    pub fn is_button_just_released(&self, button: PointerButton) -> bool {
+        self.just_released_buttons.contains(&button)
+    }
+
+    pub fn is_pressed(&self, key: KeyCode) -> bool {
+        self.pressed.contains(&key)
+    }
+
+    pub fn is_just_pressed(&self, key: KeyCode) -> bool {
+        self.just_pressed.contains(&key)
+    }
+
+    pub fn is_just_released(&self, key: KeyCode) -> bool {
+        self.just_released.contains(&key)
+    }
+
+    /// Takes over this frame's `navigator.getGamepads()`. Pads that are no longer reported
+    /// have their buttons released until their `gamepaddisconnected` arrives.
+    pub fn update_gamepads(&mut self, snapshots: &[GamepadSnapshot]) {
+        for (index, gamepad) in &mut self.gamepads {
+            if !snapshots.iter().any(|snapshot| snapshot.index == *index) {
+                gamepad.release_all();
+            }
+        }
+
+        for snapshot in snapshots {
+            self.gamepads
+                .entry(snapshot.index)
+                .or_default()
+                .update(snapshot, self.gamepad_deadzone);
+        }
+    }
+
+    /// Records a `gamepadconnected` or `gamepaddisconnected`.
+    pub fn gamepad_connection(&mut self, event: &GamepadConnection) {
+        if event.connected {
+            self.gamepads.entry(event.index).or_default();
+        } else {
+            self.gamepads.remove(&event.index);
+        }
+    }
+
+    /// How far a stick has to move before it reads anything, `0.0..1.0`.
+    pub fn set_gamepad_deadzone(&mut self, deadzone: f32) {
+        self.gamepad_deadzone = deadzone.clamp(0.0, 0.99);
+    }
+
+    pub fn gamepad(&self, index: u32) -> Option<&GamepadState> {
+        self.gamepads.get(&index)
+    }
+
+    /// Every connected gamepad by its `Gamepad.index`.
+    pub fn gamepads(&self) -> impl Iterator<Item = (u32, &GamepadState)> {
+        self.gamepads.iter().map(|(index, gamepad)| (*index, gamepad))
+    }
+
+    /// Whether `button` is held on any connected gamepad.
+    pub fn is_gamepad_button_pressed(&self, button: GamepadButton) -> bool {
+        self.gamepads.values().any(|gamepad| gamepad.is_pressed(button))
+    }
+
+    /// Whether `button` went down on any connected gamepad since the previous frame.
+    pub fn is_gamepad_button_just_pressed(&self, button: GamepadButton) -> bool {
+        self.gamepads.values().any(|gamepad| gamepad.is_just_pressed(button))
+    }
+
+    /// `axis` on whichever connected gamepad pushes it furthest, so any pad can steer.
+    pub fn gamepad_axis(&self, axis: GamepadAxis) -> f32 {
+        self.gamepads
+            .values()
+            .map(|gamepad| gamepad.axis(axis))
+            .fold(0.0, |strongest, value| if value.abs() > strongest.abs() { value } else { strongest })
+    }
+
+    /// Whether anything is held or in motion that should keep moving the scene: keys, pointer
+    /// buttons, touch contacts, gamepad buttons or sticks outside the deadzone. A gamepad that
+    /// is merely connected doesn't count; it is only read again once a frame is drawn.
+    pub fn is_active(&self) -> bool {
+        !self.pressed.is_empty()
+            || !self.buttons.is_empty()
+            || self.recognizer.active_pointers() > 0
+            || self.gamepads.values().any(GamepadState::is_active)
+    }
+
+    /// `1.0` if only `positive` is held, `-1.0` if only `negative` is, `0.0` otherwise.
+    pub fn axis(&self, negative: &[KeyCode], positive: &[KeyCode]) -> f32 {
+        let held = |keys: &[KeyCode]| keys.iter().any(|key| self.is_pressed(*key));
+
+        match (held(negative), held(positive)) {
+            (false, true) => 1.0,
+            (true, false) => -1.0,
+            _ => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::InputState;
+    use crate::utils::helpers::testing::touch;
+    use crate::utils::types::gamepad::{GamepadButtonState, GamepadConnection, GamepadSnapshot};
+    use crate::utils::types::keycode::KeyCode;
+    use crate::utils::types::pointer_event::{PointerButton, PointerKind};
+
+    fn gamepad(buttons: &[bool], axes: [f32; 4]) -> GamepadSnapshot {
+        GamepadSnapshot {
+            index: 0,
+            id: "test pad".to_string(),
+            standard: true,
+            buttons: buttons
+                .iter()
+                .map(|&pressed| GamepadButtonState {
+                    pressed,
+                    value: if pressed { 1.0 } else { 0.0 },
+                })
+                .collect(),
+            axes: axes.to_vec(),
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn idle_by_default() {
+        assert!(!InputState::default().is_active());
+    }
+
+    #[wasm_bindgen_test]
+    fn active_while_a_key_is_held() {
+        let mut input = InputState::default();
+        input.press(KeyCode::KeyCodeW, false);
+        assert!(input.is_active());
+
+        input.release(KeyCode::KeyCodeW);
+        assert!(!input.is_active());
+
+        input.press(KeyCode::KeyCodeW, false);
+        input.release_all();
+        assert!(!input.is_active());
+    }
+
+    #[wasm_bindgen_test]
+    fn active_while_a_pointer_button_or_contact_is_down() {
+        let mut input = InputState::default();
+        let mut click = touch(1, 50.0, 50.0, 0.0);
+        click.kind = PointerKind::Mouse;
+        click.button = Some(PointerButton::Secondary);
+
+        input.pointer_down(&click);
+        assert!(input.is_active());
+        input.pointer_up(&click);
+        assert!(!input.is_active());
+
+        // moving the mouse without a button held is a single frame's worth of change
+        click.button = None;
+        input.pointer_move(&click);
+        assert!(!input.is_active());
+
+        input.pointer_down(&touch(2, 50.0, 50.0, 0.0));
+        assert!(input.is_active());
+        input.pointer_cancel(&touch(2, 50.0, 50.0, 10.0));
+        assert!(!input.is_active());
+    }
+
+    #[wasm_bindgen_test]
+    fn a_connected_gamepad_at_rest_is_idle() {
+        let mut input = InputState::default();
+        input.gamepad_connection(&GamepadConnection {
+            index: 0,
+            id: "test pad".to_string(),
+            connected: true,
+        });
+        assert!(!input.is_active());
+
+        // sticks rarely rest exactly at the center
+        input.update_gamepads(&[gamepad(&[false; 4], [0.05, -0.1, 0.0, 0.08])]);
+        assert!(!input.is_active());
+    }
+
+    #[wasm_bindgen_test]
+    fn active_while_a_gamepad_button_or_stick_is_pushed() {
+        let mut input = InputState::default();
+
+        input.update_gamepads(&[gamepad(&[true, false, false, false], [0.0; 4])]);
+        assert!(input.is_active());
+        input.update_gamepads(&[gamepad(&[false; 4], [0.0; 4])]);
+        assert!(!input.is_active());
+
+        input.update_gamepads(&[gamepad(&[false; 4], [0.0, 0.0, 0.6, 0.0])]);
+        assert!(input.is_active());
+
+        // a pad that stops being reported mid-press is released
+        input.update_gamepads(&[]);
+        assert!(!input.is_active());
+    }
+}
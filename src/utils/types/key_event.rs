@@ -0,0 +1,69 @@
+use bitflags::bitflags;
+
+use crate::utils::types::keycode::KeyCode;
+
+bitflags! {
+    /// Modifier keys held while a key event fired.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    pub struct Modifiers: u8 {
+        const SHIFT = 1 << 0;
+        const CONTROL = 1 << 1;
+        const ALT = 1 << 2;
+        const META = 1 << 3;
+    }
+}
+
+impl Modifiers {
+    pub fn from_event(event: &leptos::web_sys::KeyboardEvent) -> Self {
+        Self::from_keys(event.shift_key(), event.ctrl_key(), event.alt_key(), event.meta_key())
+    }
+
+    pub fn from_mouse_event(event: &leptos::web_sys::MouseEvent) -> Self {
+        Self::from_keys(event.shift_key(), event.ctrl_key(), event.alt_key(), event.meta_key())
+    }
+
+    // keyboard and mouse events report the same four flags, but share no interface for them
+    fn from_keys(shift: bool, control: bool, alt: bool, meta: bool) -> Self {
+        let mut modifiers = Modifiers::empty();
+        modifiers.set(Modifiers::SHIFT, shift);
+        modifiers.set(Modifiers::CONTROL, control);
+        modifiers.set(Modifiers::ALT, alt);
+        modifiers.set(Modifiers::META, meta);
+        modifiers
+    }
+
+    /// The modifier `key` itself sets while it is held, e.g. [`Modifiers::SHIFT`] for either
+    /// shift key. Empty for any other key.
+    pub fn of_key(key: KeyCode) -> Self {
+        match key {
+            KeyCode::KeyCodeShiftLeft | KeyCode::KeyCodeShiftRight => Modifiers::SHIFT,
+            KeyCode::KeyCodeControlLeft | KeyCode::KeyCodeControlRight => Modifiers::CONTROL,
+            KeyCode::KeyCodeAltLeft | KeyCode::KeyCodeAltRight => Modifiers::ALT,
+            KeyCode::KeyCodeMetaLeft | KeyCode::KeyCodeMetaRight => Modifiers::META,
+            _ => Modifiers::empty(),
+        }
+    }
+}
+
+/// A `keydown` or `keyup` as handed to a [`Renderer`](crate::Renderer).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyEvent {
+    /// The key's position on the keyboard, independent of layout. Use this for movement bindings.
+    pub physical: KeyCode,
+    /// What the key means in the current layout. Use this for mnemonic shortcuts.
+    pub logical: KeyCode,
+    pub modifiers: Modifiers,
+    /// Whether this is an auto-repeat of a key that is being held down.
+    pub repeat: bool,
+}
+
+impl KeyEvent {
+    pub fn from_event(event: &leptos::web_sys::KeyboardEvent) -> Self {
+        Self {
+            physical: KeyCode::from_code(&event.code()),
+            logical: KeyCode::from_key(&event.key()),
+            modifiers: Modifiers::from_event(event),
+            repeat: event.repeat(),
+        }
+    }
+}
@@ -1,46 +1,54 @@
 use strum_macros::{EnumString, Display};
 
-#[derive(Debug, Display, EnumString)]
+/// A key on the keyboard, named after its physical position (`KeyboardEvent.code`) so that
+/// bindings such as WASD stay in place on AZERTY or Dvorak layouts.
+///
+/// Parsing also accepts the matching `KeyboardEvent.key` values, which is how the logical
+/// form of a [`KeyEvent`](crate::KeyEvent) is built. Symbols map to the main-row key that
+/// types them on a US layout, e.g. `"+"` to `Equal`, never to the numpad. `Display` gives
+/// the physical code.
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Display, EnumString, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum KeyCode {
     // Alphanumeric (a–z)
-    #[strum(serialize = "a")] KeyCodeA,
-    #[strum(serialize = "b")] KeyCodeB,
-    #[strum(serialize = "c")] KeyCodeC,
-    #[strum(serialize = "d")] KeyCodeD,
-    #[strum(serialize = "e")] KeyCodeE,
-    #[strum(serialize = "f")] KeyCodeF,
-    #[strum(serialize = "g")] KeyCodeG,
-    #[strum(serialize = "h")] KeyCodeH,
-    #[strum(serialize = "i")] KeyCodeI,
-    #[strum(serialize = "j")] KeyCodeJ,
-    #[strum(serialize = "k")] KeyCodeK,
-    #[strum(serialize = "l")] KeyCodeL,
-    #[strum(serialize = "m")] KeyCodeM,
-    #[strum(serialize = "n")] KeyCodeN,
-    #[strum(serialize = "o")] KeyCodeO,
-    #[strum(serialize = "p")] KeyCodeP,
-    #[strum(serialize = "q")] KeyCodeQ,
-    #[strum(serialize = "r")] KeyCodeR,
-    #[strum(serialize = "s")] KeyCodeS,
-    #[strum(serialize = "t")] KeyCodeT,
-    #[strum(serialize = "u")] KeyCodeU,
-    #[strum(serialize = "v")] KeyCodeV,
-    #[strum(serialize = "w")] KeyCodeW,
-    #[strum(serialize = "x")] KeyCodeX,
-    #[strum(serialize = "y")] KeyCodeY,
-    #[strum(serialize = "z")] KeyCodeZ,
+    #[strum(serialize = "KeyA", serialize = "a")] KeyCodeA,
+    #[strum(serialize = "KeyB", serialize = "b")] KeyCodeB,
+    #[strum(serialize = "KeyC", serialize = "c")] KeyCodeC,
+    #[strum(serialize = "KeyD", serialize = "d")] KeyCodeD,
+    #[strum(serialize = "KeyE", serialize = "e")] KeyCodeE,
+    #[strum(serialize = "KeyF", serialize = "f")] KeyCodeF,
+    #[strum(serialize = "KeyG", serialize = "g")] KeyCodeG,
+    #[strum(serialize = "KeyH", serialize = "h")] KeyCodeH,
+    #[strum(serialize = "KeyI", serialize = "i")] KeyCodeI,
+    #[strum(serialize = "KeyJ", serialize = "j")] KeyCodeJ,
+    #[strum(serialize = "KeyK", serialize = "k")] KeyCodeK,
+    #[strum(serialize = "KeyL", serialize = "l")] KeyCodeL,
+    #[strum(serialize = "KeyM", serialize = "m")] KeyCodeM,
+    #[strum(serialize = "KeyN", serialize = "n")] KeyCodeN,
+    #[strum(serialize = "KeyO", serialize = "o")] KeyCodeO,
+    #[strum(serialize = "KeyP", serialize = "p")] KeyCodeP,
+    #[strum(serialize = "KeyQ", serialize = "q")] KeyCodeQ,
+    #[strum(serialize = "KeyR", serialize = "r")] KeyCodeR,
+    #[strum(serialize = "KeyS", serialize = "s")] KeyCodeS,
+    #[strum(serialize = "KeyT", serialize = "t")] KeyCodeT,
+    #[strum(serialize = "KeyU", serialize = "u")] KeyCodeU,
+    #[strum(serialize = "KeyV", serialize = "v")] KeyCodeV,
+    #[strum(serialize = "KeyW", serialize = "w")] KeyCodeW,
+    #[strum(serialize = "KeyX", serialize = "x")] KeyCodeX,
+    #[strum(serialize = "KeyY", serialize = "y")] KeyCodeY,
+    #[strum(serialize = "KeyZ", serialize = "z")] KeyCodeZ,
 
     // Numbers (0–9)
-    #[strum(serialize = "0")] KeyCode0,
-    #[strum(serialize = "1")] KeyCode1,
-    #[strum(serialize = "2")] KeyCode2,
-    #[strum(serialize = "3")] KeyCode3,
-    #[strum(serialize = "4")] KeyCode4,
-    #[strum(serialize = "5")] KeyCode5,
-    #[strum(serialize = "6")] KeyCode6,
-    #[strum(serialize = "7")] KeyCode7,
-    #[strum(serialize = "8")] KeyCode8,
-    #[strum(serialize = "9")] KeyCode9,
+    #[strum(serialize = "Digit0", serialize = "0")] KeyCode0,
+    #[strum(serialize = "Digit1", serialize = "1")] KeyCode1,
+    #[strum(serialize = "Digit2", serialize = "2")] KeyCode2,
+    #[strum(serialize = "Digit3", serialize = "3")] KeyCode3,
+    #[strum(serialize = "Digit4", serialize = "4")] KeyCode4,
+    #[strum(serialize = "Digit5", serialize = "5")] KeyCode5,
+    #[strum(serialize = "Digit6", serialize = "6")] KeyCode6,
+    #[strum(serialize = "Digit7", serialize = "7")] KeyCode7,
+    #[strum(serialize = "Digit8", serialize = "8", serialize = "*")] KeyCode8,
+    #[strum(serialize = "Digit9", serialize = "9")] KeyCode9,
 
     // Arrow keys
     #[strum(serialize = "ArrowUp")] KeyCodeArrowUp,
@@ -48,7 +56,140 @@ pub enum KeyCode {
     #[strum(serialize = "ArrowLeft")] KeyCodeArrowLeft,
     #[strum(serialize = "ArrowRight")] KeyCodeArrowRight,
 
+    // Function keys
+    #[strum(serialize = "F1")] KeyCodeF1,
+    #[strum(serialize = "F2")] KeyCodeF2,
+    #[strum(serialize = "F3")] KeyCodeF3,
+    #[strum(serialize = "F4")] KeyCodeF4,
+    #[strum(serialize = "F5")] KeyCodeF5,
+    #[strum(serialize = "F6")] KeyCodeF6,
+    #[strum(serialize = "F7")] KeyCodeF7,
+    #[strum(serialize = "F8")] KeyCodeF8,
+    #[strum(serialize = "F9")] KeyCodeF9,
+    #[strum(serialize = "F10")] KeyCodeF10,
+    #[strum(serialize = "F11")] KeyCodeF11,
+    #[strum(serialize = "F12")] KeyCodeF12,
+
+    // Modifiers (logical values have no side, so they map to the left key)
+    #[strum(serialize = "ShiftLeft", serialize = "Shift")] KeyCodeShiftLeft,
+    #[strum(serialize = "ShiftRight")] KeyCodeShiftRight,
+    #[strum(serialize = "ControlLeft", serialize = "Control")] KeyCodeControlLeft,
+    #[strum(serialize = "ControlRight")] KeyCodeControlRight,
+    #[strum(serialize = "AltLeft", serialize = "Alt")] KeyCodeAltLeft,
+    #[strum(serialize = "AltRight")] KeyCodeAltRight,
+    #[strum(serialize = "MetaLeft", serialize = "Meta")] KeyCodeMetaLeft,
+    #[strum(serialize = "MetaRight")] KeyCodeMetaRight,
+
+    // Numpad
+    #[strum(serialize = "Numpad0")] KeyCodeNumpad0,
+    #[strum(serialize = "Numpad1")] KeyCodeNumpad1,
+    #[strum(serialize = "Numpad2")] KeyCodeNumpad2,
+    #[strum(serialize = "Numpad3")] KeyCodeNumpad3,
+    #[strum(serialize = "Numpad4")] KeyCodeNumpad4,
+    #[strum(serialize = "Numpad5")] KeyCodeNumpad5,
+    #[strum(serialize = "Numpad6")] KeyCodeNumpad6,
+    #[strum(serialize = "Numpad7")] KeyCodeNumpad7,
+    #[strum(serialize = "Numpad8")] KeyCodeNumpad8,
+    #[strum(serialize = "Numpad9")] KeyCodeNumpad9,
+    #[strum(serialize = "NumpadAdd")] KeyCodeNumpadAdd,
+    #[strum(serialize = "NumpadSubtract")] KeyCodeNumpadSubtract,
+    #[strum(serialize = "NumpadMultiply")] KeyCodeNumpadMultiply,
+    #[strum(serialize = "NumpadDivide")] KeyCodeNumpadDivide,
+    #[strum(serialize = "NumpadDecimal")] KeyCodeNumpadDecimal,
+    #[strum(serialize = "NumpadEnter")] KeyCodeNumpadEnter,
+    #[strum(serialize = "NumLock")] KeyCodeNumLock,
+
+    // Punctuation
+    #[strum(serialize = "Minus", serialize = "-")] KeyCodeMinus,
+    #[strum(serialize = "Equal", serialize = "=", serialize = "+")] KeyCodeEqual,
+    #[strum(serialize = "BracketLeft", serialize = "[")] KeyCodeBracketLeft,
+    #[strum(serialize = "BracketRight", serialize = "]")] KeyCodeBracketRight,
+    #[strum(serialize = "Backslash", serialize = "\\")] KeyCodeBackslash,
+    #[strum(serialize = "Semicolon", serialize = ";")] KeyCodeSemicolon,
+    #[strum(serialize = "Quote", serialize = "'")] KeyCodeQuote,
+    #[strum(serialize = "Backquote", serialize = "`")] KeyCodeBackquote,
+    #[strum(serialize = "Comma", serialize = ",")] KeyCodeComma,
+    #[strum(serialize = "Period", serialize = ".")] KeyCodePeriod,
+    #[strum(serialize = "Slash", serialize = "/")] KeyCodeSlash,
+
+    // Editing and navigation
+    #[strum(serialize = "Enter")] KeyCodeEnter,
+    #[strum(serialize = "Escape")] KeyCodeEscape,
+    #[strum(serialize = "Tab")] KeyCodeTab,
+    #[strum(serialize = "Backspace")] KeyCodeBackspace,
+    #[strum(serialize = "Delete")] KeyCodeDelete,
+    #[strum(serialize = "Insert")] KeyCodeInsert,
+    #[strum(serialize = "Home")] KeyCodeHome,
+    #[strum(serialize = "End")] KeyCodeEnd,
+    #[strum(serialize = "PageUp")] KeyCodePageUp,
+    #[strum(serialize = "PageDown")] KeyCodePageDown,
+    #[strum(serialize = "CapsLock")] KeyCodeCapsLock,
+    #[strum(serialize = "ContextMenu")] KeyCodeContextMenu,
+    #[strum(serialize = "PrintScreen")] KeyCodePrintScreen,
+    #[strum(serialize = "ScrollLock")] KeyCodeScrollLock,
+    #[strum(serialize = "Pause")] KeyCodePause,
+
     // misc.
-    #[strum(serialize = " ")] KeyCodeSpace,
+    #[strum(serialize = "Space", serialize = " ")] KeyCodeSpace,
     Unknown,
-}
\ No newline at end of file
+}
+
+impl KeyCode {
+    /// Maps a `KeyboardEvent.code` value, i.e. the physical key.
+    pub fn from_code(code: &str) -> Self {
+        code.parse().unwrap_or(KeyCode::Unknown)
+    }
+
+    /// Maps a `KeyboardEvent.key` value, i.e. what the key means in the current layout.
+    /// Named keys such as `"ArrowUp"` are matched as is, letters regardless of shift.
+    pub fn from_key(key: &str) -> Self {
+        key.parse()
+            .or_else(|_| key.to_ascii_lowercase().parse())
+            .unwrap_or(KeyCode::Unknown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::KeyCode;
+
+    #[wasm_bindgen_test]
+    fn from_code_maps_physical_keys() {
+        assert_eq!(KeyCode::from_code("KeyW"), KeyCode::KeyCodeW);
+        assert_eq!(KeyCode::from_code("Digit8"), KeyCode::KeyCode8);
+        assert_eq!(KeyCode::from_code("ArrowUp"), KeyCode::KeyCodeArrowUp);
+        assert_eq!(KeyCode::from_code("NumpadAdd"), KeyCode::KeyCodeNumpadAdd);
+        assert_eq!(KeyCode::from_code("ShiftRight"), KeyCode::KeyCodeShiftRight);
+        assert_eq!(KeyCode::from_code("LaunchMail"), KeyCode::Unknown);
+    }
+
+    #[wasm_bindgen_test]
+    fn from_key_maps_named_keys_and_shifted_letters() {
+        assert_eq!(KeyCode::from_key("ArrowUp"), KeyCode::KeyCodeArrowUp);
+        assert_eq!(KeyCode::from_key("Escape"), KeyCode::KeyCodeEscape);
+        assert_eq!(KeyCode::from_key("Shift"), KeyCode::KeyCodeShiftLeft);
+        assert_eq!(KeyCode::from_key(" "), KeyCode::KeyCodeSpace);
+        assert_eq!(KeyCode::from_key("z"), KeyCode::KeyCodeZ);
+        assert_eq!(KeyCode::from_key("Z"), KeyCode::KeyCodeZ);
+        assert_eq!(KeyCode::from_key("é"), KeyCode::Unknown);
+    }
+
+    #[wasm_bindgen_test]
+    fn from_key_maps_symbols_to_the_main_row() {
+        assert_eq!(KeyCode::from_key("+"), KeyCode::KeyCodeEqual);
+        assert_eq!(KeyCode::from_key("="), KeyCode::KeyCodeEqual);
+        assert_eq!(KeyCode::from_key("*"), KeyCode::KeyCode8);
+        assert_eq!(KeyCode::from_key("-"), KeyCode::KeyCodeMinus);
+        assert_eq!(KeyCode::from_key("/"), KeyCode::KeyCodeSlash);
+        assert_eq!(KeyCode::from_key("."), KeyCode::KeyCodePeriod);
+    }
+
+    #[wasm_bindgen_test]
+    fn display_gives_the_physical_code() {
+        assert_eq!(KeyCode::KeyCodeEqual.to_string(), "Equal");
+        assert_eq!(KeyCode::KeyCode8.to_string(), "Digit8");
+        assert_eq!(KeyCode::KeyCodeArrowUp.to_string(), "ArrowUp");
+    }
+}
@@ -1,3 +1,17 @@
+pub mod action_map;
+pub mod action_map_error;
 pub(crate) mod buffers;
-pub(crate) mod keycode;
+pub mod camera;
+pub mod frame_time;
+pub mod gamepad;
+pub mod gesture;
+pub mod input_state;
+pub mod key_event;
+pub mod keycode;
+pub mod pointer_event;
+pub mod render_mode;
+pub mod render_stats;
+pub mod render_status;
+pub mod scene_update;
 pub mod size;
+pub mod state_error;
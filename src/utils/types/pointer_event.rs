@@ -0,0 +1,163 @@
+use crate::utils::types::key_event::Modifiers;
+use crate::utils::types::size::{PhysicalPosition, PhysicalSize};
+
+/// A mouse button, pen button or touch contact, as numbered by `PointerEvent.button`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointerButton {
+    /// Usually the left mouse button, or any touch or pen contact.
+    Primary,
+    /// Usually the middle mouse button.
+    Auxiliary,
+    /// Usually the right mouse button.
+    Secondary,
+    Back,
+    Forward,
+    Other(i16),
+}
+
+impl PointerButton {
+    pub fn from_button(button: i16) -> Self {
+        match button {
+            0 => PointerButton::Primary,
+            1 => PointerButton::Auxiliary,
+            2 => PointerButton::Secondary,
+            3 => PointerButton::Back,
+            4 => PointerButton::Forward,
+            other => PointerButton::Other(other),
+        }
+    }
+}
+
+/// What kind of device produced a [`PointerEvent`], from `PointerEvent.pointerType`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PointerKind {
+    #[default]
+    Mouse,
+    Pen,
+    Touch,
+}
+
+impl PointerKind {
+    pub fn from_pointer_type(pointer_type: &str) -> Self {
+        match pointer_type {
+            "pen" => PointerKind::Pen,
+            "touch" => PointerKind::Touch,
+            _ => PointerKind::Mouse,
+        }
+    }
+}
+
+/// Where a pointer event happened, both in canvas pixels and in NDC.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PointerPosition {
+    /// Relative to the canvas's top-left corner, in the same pixels as its backing store.
+    pub physical: PhysicalPosition<f64>,
+    /// `-1.0..=1.0` across the canvas, `y` up.
+    pub ndc: [f32; 2],
+}
+
+impl PointerPosition {
+    /// Maps the event's client coordinates onto `canvas`, going through its bounding rect so
+    /// CSS borders, transforms and the backing-store scale are all accounted for.
+    pub fn from_event(event: &leptos::web_sys::MouseEvent, canvas: &leptos::web_sys::HtmlCanvasElement) -> Self {
+        Self::from_rect(event, canvas, &canvas.get_bounding_client_rect())
+    }
+
+    /// [`PointerPosition::from_event`] with the canvas's bounding rect already read, since
+    /// every read forces a layout.
+    fn from_rect(
+        event: &leptos::web_sys::MouseEvent,
+        canvas: &leptos::web_sys::HtmlCanvasElement,
+        rect: &leptos::web_sys::DomRect,
+    ) -> Self {
+        let size = PhysicalSize {
+            width: canvas.width(),
+            height: canvas.height(),
+        };
+
+        let [scale_x, scale_y] = canvas_scale(canvas, rect);
+        let physical = PhysicalPosition {
+            x: (event.client_x() as f64 - rect.left()) * scale_x,
+            y: (event.client_y() as f64 - rect.top()) * scale_y,
+        };
+
+        Self {
+            physical,
+            ndc: physical.to_ndc(size),
+        }
+    }
+}
+
+/// Backing-store pixels per CSS pixel of `canvas`, horizontally and vertically, given its
+/// bounding `rect`.
+fn canvas_scale(canvas: &leptos::web_sys::HtmlCanvasElement, rect: &leptos::web_sys::DomRect) -> [f64; 2] {
+    let scale = |physical: u32, css: f64| if css > 0.0 { physical as f64 / css } else { 1.0 };
+
+    [scale(canvas.width(), rect.width()), scale(canvas.height(), rect.height())]
+}
+
+/// A `pointerdown`, `pointerup` or `pointermove` on the canvas, as handed to a
+/// [`Renderer`](crate::Renderer).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointerEvent {
+    /// Tells apart simultaneous pointers, e.g. two fingers on a touch screen.
+    pub pointer_id: i32,
+    pub kind: PointerKind,
+    pub position: PointerPosition,
+    /// The button that changed, or `None` for a plain move.
+    pub button: Option<PointerButton>,
+    /// `movementX`/`movementY` in canvas pixels: how far the pointer moved since its last
+    /// event. Unlike [`PointerEvent::position`] this keeps changing while the pointer is locked.
+    pub movement: [f64; 2],
+    pub modifiers: Modifiers,
+    /// `Event.timeStamp`, in milliseconds.
+    pub time_stamp: f64,
+}
+
+impl PointerEvent {
+    pub fn from_event(event: &leptos::web_sys::PointerEvent, canvas: &leptos::web_sys::HtmlCanvasElement) -> Self {
+        let rect = canvas.get_bounding_client_rect();
+        let [scale_x, scale_y] = canvas_scale(canvas, &rect);
+
+        Self {
+            pointer_id: event.pointer_id(),
+            kind: PointerKind::from_pointer_type(&event.pointer_type()),
+            position: PointerPosition::from_rect(event, canvas, &rect),
+            // `button` is -1 when no button changed
+            button: (event.button() >= 0).then(|| PointerButton::from_button(event.button())),
+            movement: [event.movement_x() as f64 * scale_x, event.movement_y() as f64 * scale_y],
+            modifiers: Modifiers::from_mouse_event(event),
+            time_stamp: event.time_stamp(),
+        }
+    }
+}
+
+/// A `wheel` on the canvas, with the scroll distance normalized to pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WheelEvent {
+    pub position: PointerPosition,
+    pub delta_x: f64,
+    /// Positive when scrolling down or pulling the trackpad towards the user.
+    pub delta_y: f64,
+    pub modifiers: Modifiers,
+}
+
+impl WheelEvent {
+    /// Roughly how many pixels one line of scrolling is worth, for `DOM_DELTA_LINE` wheels.
+    const LINE_HEIGHT: f64 = 16.0;
+
+    pub fn from_event(event: &leptos::web_sys::WheelEvent, canvas: &leptos::web_sys::HtmlCanvasElement) -> Self {
+        let scale = match event.delta_mode() {
+            leptos::web_sys::WheelEvent::DOM_DELTA_LINE => Self::LINE_HEIGHT,
+            leptos::web_sys::WheelEvent::DOM_DELTA_PAGE => canvas.client_height() as f64,
+            _ => 1.0,
+        };
+
+        Self {
+            position: PointerPosition::from_event(event, canvas),
+            delta_x: event.delta_x() * scale,
+            delta_y: event.delta_y() * scale,
+            modifiers: Modifiers::from_mouse_event(event),
+        }
+    }
+}
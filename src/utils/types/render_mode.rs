@@ -0,0 +1,39 @@
+use std::fmt;
+use std::rc::Rc;
+
+/// When a [`WgpuCanvas`](crate::WgpuCanvas) draws frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderMode {
+    /// A frame on every `requestAnimationFrame`, for animated scenes.
+    #[default]
+    Continuous,
+    /// A frame only after a [`RedrawHandle::request_redraw`], an input event, a resize or a
+    /// scene signal change, and for as long as [`Renderer::wants_redraw`](crate::Renderer::wants_redraw)
+    /// says so. For static views that only change on input.
+    OnDemand,
+}
+
+/// Asks the canvas for another frame. Cheap to clone; requests made while a frame is already
+/// scheduled are merged into it.
+#[derive(Clone)]
+pub struct RedrawHandle {
+    request: Rc<dyn Fn()>,
+}
+
+impl RedrawHandle {
+    pub(crate) fn new(request: impl Fn() + 'static) -> Self {
+        Self {
+            request: Rc::new(request),
+        }
+    }
+
+    pub fn request_redraw(&self) {
+        (self.request)();
+    }
+}
+
+impl fmt::Debug for RedrawHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedrawHandle").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,16 @@
+use crate::utils::types::size::PhysicalSize;
+
+/// Rendering health published by a [`WgpuCanvas`](crate::WgpuCanvas) a few times per second.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RenderStats {
+    /// Time between the last two frames, in milliseconds.
+    pub frame_time_ms: f64,
+    /// Frames per second, smoothed over roughly the last second.
+    pub fps: f64,
+    /// Name of the adapter, e.g. the GPU model; empty until the renderer reports it.
+    pub adapter_name: String,
+    /// The backend the adapter runs on, e.g. `BrowserWebGpu` or `Gl`.
+    pub backend: String,
+    /// Size of the canvas backing store the surface renders to.
+    pub surface_size: PhysicalSize<u32>,
+}
@@ -0,0 +1,53 @@
+use std::fmt;
+
+/// What a [`WgpuCanvas`](crate::WgpuCanvas) is currently doing, for the host page to display.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum RenderStatus {
+    /// The renderer is still being created.
+    #[default]
+    Initializing,
+    /// Frames are being presented normally.
+    Running,
+    /// The surface was lost or outdated and has been reconfigured.
+    Reconfigured,
+    /// The last frame was dropped because the surface did not hand out a texture in time.
+    SkippedFrame,
+    /// The loop is suspended because the canvas is offscreen or the tab is hidden.
+    Suspended,
+    /// Rendering stopped after an unrecoverable error.
+    Stopped(String),
+}
+
+impl fmt::Display for RenderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderStatus::Initializing => write!(f, "Initializing"),
+            RenderStatus::Running => write!(f, "Running"),
+            RenderStatus::Reconfigured => write!(f, "Surface reconfigured"),
+            RenderStatus::SkippedFrame => write!(f, "Skipped frame"),
+            RenderStatus::Suspended => write!(f, "Suspended"),
+            RenderStatus::Stopped(reason) => write!(f, "Stopped: {reason}"),
+        }
+    }
+}
+
+/// How the render loop reacts to a failed [`Renderer::render`](crate::Renderer::render).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Recovery {
+    /// Reconfigure the surface and carry on with the next frame.
+    Reconfigure,
+    /// Drop this frame and try again on the next one.
+    Skip,
+    /// Stop the loop and report the error.
+    Stop,
+}
+
+impl From<&wgpu::SurfaceError> for Recovery {
+    fn from(error: &wgpu::SurfaceError) -> Self {
+        match error {
+            wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated => Recovery::Reconfigure,
+            wgpu::SurfaceError::Timeout | wgpu::SurfaceError::Other => Recovery::Skip,
+            wgpu::SurfaceError::OutOfMemory => Recovery::Stop,
+        }
+    }
+}
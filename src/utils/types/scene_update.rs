@@ -0,0 +1,16 @@
+/// The [`Renderer::Params`](crate::Renderer::Params) of [`State`](crate::State): scene settings
+/// the page changes through the canvas `params` prop. Unset fields are left as they are.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SceneUpdate {
+    pub clear_color: Option<wgpu::Color>,
+    /// Clamped to `3..=`[`State::MAX_POLYGON_SIDES`](crate::State::MAX_POLYGON_SIDES).
+    pub polygon_sides: Option<u16>,
+    pub polygon_radius: Option<f32>,
+    pub vertex_color: Option<[f32; 3]>,
+}
+
+impl SceneUpdate {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
@@ -1,4 +1,114 @@
+/// A size in device pixels, e.g. the canvas backing store or the surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct PhysicalSize<T> {
     pub width: T,
     pub height: T,
-}
\ No newline at end of file
+}
+
+/// A size in CSS pixels, which is what layout and `client_width`/`client_height` use.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LogicalSize<T> {
+    pub width: T,
+    pub height: T,
+}
+
+impl PhysicalSize<u32> {
+    /// Width over height, or `1.0` while the size is still empty.
+    pub fn aspect(&self) -> f32 {
+        if self.height == 0 {
+            1.0
+        } else {
+            self.width as f32 / self.height as f32
+        }
+    }
+
+    pub fn to_logical(&self, scale_factor: f64) -> LogicalSize<f64> {
+        LogicalSize {
+            width: self.width as f64 / scale_factor,
+            height: self.height as f64 / scale_factor,
+        }
+    }
+
+    /// Scales the size down until neither side exceeds `max`, e.g. the device's
+    /// `max_texture_dimension_2d`. Both sides shrink by the same factor so the aspect ratio
+    /// stays that of the CSS box; a size that already fits is returned as is.
+    pub fn clamp(&self, max: u32) -> Self {
+        let longer = self.width.max(self.height);
+        if longer <= max {
+            return *self;
+        }
+
+        let factor = max as f64 / longer as f64;
+        // a side that isn't empty stays at least a pixel wide
+        let scale = |side: u32| ((side as f64 * factor).round() as u32).clamp(side.min(1), max);
+        Self {
+            width: scale(self.width),
+            height: scale(self.height),
+        }
+    }
+}
+
+impl LogicalSize<f64> {
+    pub fn to_physical(&self, scale_factor: f64) -> PhysicalSize<u32> {
+        PhysicalSize {
+            width: (self.width * scale_factor).round() as u32,
+            height: (self.height * scale_factor).round() as u32,
+        }
+    }
+}
+
+/// A point in device pixels, relative to the top-left corner of the canvas backing store.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PhysicalPosition<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl PhysicalPosition<f64> {
+    /// Normalized device coordinates within a canvas of `size`: `-1.0..=1.0` on both axes,
+    /// with `y` pointing up as it does in clip space.
+    pub fn to_ndc(&self, size: PhysicalSize<u32>) -> [f32; 2] {
+        if size.width == 0 || size.height == 0 {
+            return [0.0, 0.0];
+        }
+
+        [
+            (2.0 * self.x / size.width as f64 - 1.0) as f32,
+            (1.0 - 2.0 * self.y / size.height as f64) as f32,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::PhysicalSize;
+
+    #[wasm_bindgen_test]
+    fn clamp_keeps_a_size_that_fits() {
+        let size = PhysicalSize { width: 1920, height: 1080 };
+        assert_eq!(size.clamp(2048), size);
+        assert_eq!(size.clamp(1920), size);
+    }
+
+    #[wasm_bindgen_test]
+    fn clamp_scales_both_sides_by_the_same_factor() {
+        let wide = PhysicalSize { width: 10000, height: 5000 };
+        assert_eq!(wide.clamp(8192), PhysicalSize { width: 8192, height: 4096 });
+
+        let tall = PhysicalSize { width: 3000, height: 9000 };
+        let clamped = tall.clamp(2048);
+        assert_eq!(clamped, PhysicalSize { width: 683, height: 2048 });
+        assert!((clamped.aspect() - tall.aspect()).abs() < 1e-3);
+    }
+
+    #[wasm_bindgen_test]
+    fn clamp_keeps_a_thin_side_visible() {
+        let sliver = PhysicalSize { width: 100000, height: 1 };
+        assert_eq!(sliver.clamp(4096), PhysicalSize { width: 4096, height: 1 });
+
+        let empty = PhysicalSize { width: 100000, height: 0 };
+        assert_eq!(empty.clamp(4096), PhysicalSize { width: 4096, height: 0 });
+    }
+}
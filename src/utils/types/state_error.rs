@@ -0,0 +1,63 @@
+use std::fmt;
+
+/// Reasons [`State::new`](crate::utils::state::State::new) can fail to bring up the GPU.
+#[derive(Debug, Clone)]
+pub enum StateError {
+    /// The [`GpuConfig`](crate::GpuConfig) did not leave any backend to try.
+    NoBackends,
+    /// The canvas could not be turned into a `wgpu::Surface`.
+    Surface(wgpu::CreateSurfaceError),
+    /// No adapter compatible with the canvas surface was found.
+    Adapter(wgpu::RequestAdapterError),
+    /// The adapter refused to hand out a device with the requested features and limits.
+    Device(wgpu::RequestDeviceError),
+    /// A shader module or the pipeline built from it failed validation.
+    Shader(String),
+    /// The device was lost and every attempt at creating a new one failed; holds the error of the last attempt.
+    DeviceLost { attempts: u32, last_error: Box<StateError> },
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StateError::NoBackends => write!(f, "No graphics backend is available to try"),
+            StateError::Surface(e) => write!(f, "Could not create a surface for the canvas: {e}"),
+            StateError::Adapter(e) => write!(f, "No compatible graphics adapter was found: {e}"),
+            StateError::Device(e) => write!(f, "Could not create a graphics device: {e}"),
+            StateError::Shader(e) => write!(f, "Shader failed to compile: {e}"),
+            StateError::DeviceLost { attempts, last_error } => {
+                write!(f, "The graphics device was lost and could not be recreated after {attempts} attempts: {last_error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for StateError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StateError::Surface(e) => Some(e),
+            StateError::Adapter(e) => Some(e),
+            StateError::Device(e) => Some(e),
+            StateError::DeviceLost { last_error, .. } => Some(last_error.as_ref()),
+            StateError::NoBackends | StateError::Shader(_) => None,
+        }
+    }
+}
+
+impl From<wgpu::CreateSurfaceError> for StateError {
+    fn from(e: wgpu::CreateSurfaceError) -> Self {
+        StateError::Surface(e)
+    }
+}
+
+impl From<wgpu::RequestAdapterError> for StateError {
+    fn from(e: wgpu::RequestAdapterError) -> Self {
+        StateError::Adapter(e)
+    }
+}
+
+impl From<wgpu::RequestDeviceError> for StateError {
+    fn from(e: wgpu::RequestDeviceError) -> Self {
+        StateError::Device(e)
+    }
+}